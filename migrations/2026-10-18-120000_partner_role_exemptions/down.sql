@@ -0,0 +1,2 @@
+DROP TABLE partner_role_exempt_roles;
+DROP TABLE partner_role_exempt_users;
\ No newline at end of file
@@ -0,0 +1,11 @@
+CREATE TABLE partner_role_exempt_users (
+	guild_id BIGINT NOT NULL REFERENCES guild_settings,
+	user_id BIGINT NOT NULL,
+	PRIMARY KEY (guild_id, user_id)
+);
+
+CREATE TABLE partner_role_exempt_roles (
+	guild_id BIGINT NOT NULL REFERENCES guild_settings,
+	role_id BIGINT NOT NULL,
+	PRIMARY KEY (guild_id, role_id)
+);
\ No newline at end of file
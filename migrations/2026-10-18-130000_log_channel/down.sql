@@ -0,0 +1 @@
+ALTER TABLE guild_settings DROP COLUMN log_channel;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE guild_settings ADD COLUMN log_channel BIGINT;
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE guild_settings DROP COLUMN remove_announcement_template;
+ALTER TABLE guild_settings DROP COLUMN add_announcement_template;
+ALTER TABLE guild_settings DROP COLUMN announcement_channel;
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE guild_settings ADD COLUMN announcement_channel BIGINT;
+ALTER TABLE guild_settings ADD COLUMN add_announcement_template TEXT NOT NULL DEFAULT 'Welcome our new partner, {name}! {invite}';
+ALTER TABLE guild_settings ADD COLUMN remove_announcement_template TEXT NOT NULL DEFAULT '';
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE published_messages DROP COLUMN channel_id;
+ALTER TABLE embed_data DROP COLUMN channel;
\ No newline at end of file
@@ -0,0 +1,4 @@
+ALTER TABLE embed_data ADD COLUMN channel BIGINT;
+ALTER TABLE published_messages ADD COLUMN channel_id BIGINT;
+UPDATE published_messages SET channel_id = guild_settings.publish_channel FROM guild_settings WHERE published_messages.guild_id = guild_settings.guild_id;
+ALTER TABLE published_messages ALTER COLUMN channel_id SET NOT NULL;
\ No newline at end of file
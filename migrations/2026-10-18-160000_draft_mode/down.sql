@@ -0,0 +1,2 @@
+DROP TABLE published_embed_summaries;
+ALTER TABLE guild_settings DROP COLUMN draft_mode;
\ No newline at end of file
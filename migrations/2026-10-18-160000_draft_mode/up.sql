@@ -0,0 +1,8 @@
+ALTER TABLE guild_settings ADD COLUMN draft_mode BOOLEAN NOT NULL DEFAULT false;
+
+CREATE TABLE published_embed_summaries (
+	guild_id BIGINT NOT NULL REFERENCES guild_settings,
+	embed_id TEXT NOT NULL,
+	summary TEXT NOT NULL,
+	PRIMARY KEY (guild_id, embed_id)
+);
\ No newline at end of file
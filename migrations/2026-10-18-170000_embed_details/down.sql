@@ -0,0 +1,8 @@
+ALTER TABLE embed_data DROP COLUMN show_timestamp;
+ALTER TABLE embed_data DROP COLUMN footer_icon_url;
+ALTER TABLE embed_data DROP COLUMN footer_text;
+ALTER TABLE embed_data DROP COLUMN author_icon_url;
+ALTER TABLE embed_data DROP COLUMN author_name;
+ALTER TABLE embed_data DROP COLUMN thumbnail_url;
+ALTER TABLE embed_data DROP COLUMN title_url;
+ALTER TABLE embed_data DROP COLUMN title;
\ No newline at end of file
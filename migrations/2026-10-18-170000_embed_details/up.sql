@@ -0,0 +1,8 @@
+ALTER TABLE embed_data ADD COLUMN title TEXT NOT NULL DEFAULT '';
+ALTER TABLE embed_data ADD COLUMN title_url TEXT NOT NULL DEFAULT '';
+ALTER TABLE embed_data ADD COLUMN thumbnail_url TEXT NOT NULL DEFAULT '';
+ALTER TABLE embed_data ADD COLUMN author_name TEXT NOT NULL DEFAULT '';
+ALTER TABLE embed_data ADD COLUMN author_icon_url TEXT NOT NULL DEFAULT '';
+ALTER TABLE embed_data ADD COLUMN footer_text TEXT NOT NULL DEFAULT '';
+ALTER TABLE embed_data ADD COLUMN footer_icon_url TEXT NOT NULL DEFAULT '';
+ALTER TABLE embed_data ADD COLUMN show_timestamp BOOLEAN NOT NULL DEFAULT false;
\ No newline at end of file
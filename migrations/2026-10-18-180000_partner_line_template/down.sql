@@ -0,0 +1 @@
+ALTER TABLE embed_data DROP COLUMN partner_line_template;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE embed_data ADD COLUMN partner_line_template TEXT NOT NULL DEFAULT '- [{name}]({invite})';
\ No newline at end of file
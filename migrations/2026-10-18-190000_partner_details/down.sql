@@ -0,0 +1,2 @@
+ALTER TABLE partners DROP COLUMN emoji;
+ALTER TABLE partners DROP COLUMN description;
\ No newline at end of file
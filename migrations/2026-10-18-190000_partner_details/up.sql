@@ -0,0 +1,2 @@
+ALTER TABLE partners ADD COLUMN description TEXT NOT NULL DEFAULT '';
+ALTER TABLE partners ADD COLUMN emoji TEXT NOT NULL DEFAULT '';
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE published_messages DROP COLUMN content_hash;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE published_messages ADD COLUMN content_hash BIGINT;
\ No newline at end of file
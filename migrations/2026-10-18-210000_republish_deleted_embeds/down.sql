@@ -0,0 +1 @@
+ALTER TABLE guild_settings DROP COLUMN republish_deleted_messages;
\ No newline at end of file
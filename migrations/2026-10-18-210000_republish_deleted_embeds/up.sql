@@ -0,0 +1 @@
+ALTER TABLE guild_settings ADD COLUMN republish_deleted_messages BOOLEAN NOT NULL DEFAULT true;
\ No newline at end of file
@@ -0,0 +1,8 @@
+ALTER TABLE published_messages DROP COLUMN webhook_identity_hash;
+ALTER TABLE published_messages DROP COLUMN webhook_id;
+
+DROP TABLE publish_webhooks;
+
+ALTER TABLE guild_settings DROP COLUMN webhook_avatar_url;
+ALTER TABLE guild_settings DROP COLUMN webhook_username;
+ALTER TABLE guild_settings DROP COLUMN webhook_publishing;
\ No newline at end of file
@@ -0,0 +1,13 @@
+ALTER TABLE guild_settings ADD COLUMN webhook_publishing BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE guild_settings ADD COLUMN webhook_username TEXT NOT NULL DEFAULT '';
+ALTER TABLE guild_settings ADD COLUMN webhook_avatar_url TEXT NOT NULL DEFAULT '';
+
+CREATE TABLE publish_webhooks (
+	channel_id BIGINT PRIMARY KEY,
+	guild_id BIGINT NOT NULL REFERENCES guild_settings,
+	webhook_id BIGINT NOT NULL,
+	webhook_url TEXT NOT NULL
+);
+
+ALTER TABLE published_messages ADD COLUMN webhook_id BIGINT;
+ALTER TABLE published_messages ADD COLUMN webhook_identity_hash BIGINT;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE published_messages DROP COLUMN thread_id;
+ALTER TABLE guild_settings DROP COLUMN crosspost_announcements;
\ No newline at end of file
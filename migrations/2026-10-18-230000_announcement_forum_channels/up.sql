@@ -0,0 +1,2 @@
+ALTER TABLE guild_settings ADD COLUMN crosspost_announcements BOOLEAN NOT NULL DEFAULT false;
+ALTER TABLE published_messages ADD COLUMN thread_id BIGINT;
\ No newline at end of file
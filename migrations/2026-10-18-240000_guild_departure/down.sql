@@ -0,0 +1 @@
+ALTER TABLE guild_settings DROP COLUMN departed_at;
\ No newline at end of file
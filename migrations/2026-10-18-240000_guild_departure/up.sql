@@ -0,0 +1 @@
+ALTER TABLE guild_settings ADD COLUMN departed_at TIMESTAMP;
\ No newline at end of file
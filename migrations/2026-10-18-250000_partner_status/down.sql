@@ -0,0 +1 @@
+ALTER TABLE partners DROP COLUMN status;
\ No newline at end of file
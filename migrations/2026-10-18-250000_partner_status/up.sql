@@ -0,0 +1 @@
+ALTER TABLE partners ADD COLUMN status TEXT NOT NULL DEFAULT 'active' CONSTRAINT valid_partner_status CHECK (status IN ('pending', 'active', 'hiatus', 'ended'));
\ No newline at end of file
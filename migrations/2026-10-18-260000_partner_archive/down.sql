@@ -0,0 +1,3 @@
+DROP TABLE archived_partner_self_users;
+DROP TABLE archived_partner_users;
+DROP TABLE archived_partners;
\ No newline at end of file
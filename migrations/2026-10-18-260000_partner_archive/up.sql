@@ -0,0 +1,26 @@
+CREATE TABLE archived_partners (
+	partnership_id TEXT PRIMARY KEY,
+	guild BIGINT NOT NULL REFERENCES guild_settings,
+	category TEXT REFERENCES partner_categories ON DELETE SET NULL,
+	partner_guild BIGINT NOT NULL,
+	display_name TEXT NOT NULL,
+	invite_code TEXT NOT NULL,
+	description TEXT NOT NULL,
+	emoji TEXT NOT NULL,
+	status TEXT NOT NULL,
+	removal_reason TEXT NOT NULL,
+	removed_at TIMESTAMP NOT NULL,
+	removed_by BIGINT
+);
+
+CREATE TABLE archived_partner_users (
+	partnership_id TEXT NOT NULL REFERENCES archived_partners ON DELETE CASCADE,
+	user_id BIGINT NOT NULL,
+	PRIMARY KEY (partnership_id, user_id)
+);
+
+CREATE TABLE archived_partner_self_users (
+	partnership TEXT NOT NULL REFERENCES archived_partners ON DELETE CASCADE,
+	user_id BIGINT NOT NULL,
+	PRIMARY KEY (partnership, user_id)
+);
\ No newline at end of file
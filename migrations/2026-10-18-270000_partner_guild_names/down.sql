@@ -0,0 +1,2 @@
+ALTER TABLE archived_partners DROP COLUMN partner_guild_name;
+ALTER TABLE partners DROP COLUMN partner_guild_name;
\ No newline at end of file
@@ -0,0 +1,2 @@
+ALTER TABLE partners ADD COLUMN partner_guild_name TEXT NOT NULL DEFAULT '';
+ALTER TABLE archived_partners ADD COLUMN partner_guild_name TEXT NOT NULL DEFAULT '';
\ No newline at end of file
@@ -0,0 +1 @@
+DROP TABLE partnership_requests;
\ No newline at end of file
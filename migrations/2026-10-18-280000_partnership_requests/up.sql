@@ -0,0 +1,12 @@
+CREATE TABLE partnership_requests (
+	id TEXT PRIMARY KEY,
+	requesting_guild BIGINT NOT NULL REFERENCES guild_settings,
+	requested_guild BIGINT NOT NULL REFERENCES guild_settings,
+	requesting_category TEXT NOT NULL REFERENCES partner_categories ON DELETE CASCADE,
+	requester BIGINT NOT NULL,
+	requesting_guild_name TEXT NOT NULL,
+	requesting_invite_code TEXT NOT NULL,
+	requested_guild_name TEXT NOT NULL,
+	requested_invite_code TEXT NOT NULL,
+	CONSTRAINT unique_partnership_request UNIQUE (requesting_guild, requested_guild)
+);
\ No newline at end of file
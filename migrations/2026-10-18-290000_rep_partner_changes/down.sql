@@ -0,0 +1,2 @@
+DROP TABLE pending_partner_changes;
+ALTER TABLE guild_settings DROP COLUMN rep_changes_need_approval;
\ No newline at end of file
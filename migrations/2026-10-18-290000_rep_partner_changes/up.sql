@@ -0,0 +1,11 @@
+ALTER TABLE guild_settings ADD COLUMN rep_changes_need_approval BOOLEAN NOT NULL DEFAULT TRUE;
+CREATE TABLE pending_partner_changes (
+	id TEXT PRIMARY KEY,
+	partnership_id TEXT NOT NULL REFERENCES partners ON DELETE CASCADE,
+	requested_by BIGINT NOT NULL,
+	display_name TEXT,
+	description TEXT,
+	invite_code TEXT,
+	partner_guild_name TEXT,
+	requested_at TIMESTAMP NOT NULL
+);
\ No newline at end of file
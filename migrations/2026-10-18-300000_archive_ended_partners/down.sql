@@ -0,0 +1,2 @@
+ALTER TABLE partners DROP CONSTRAINT valid_partner_status;
+ALTER TABLE partners ADD CONSTRAINT valid_partner_status CHECK (status IN ('pending', 'active', 'hiatus', 'ended'));
\ No newline at end of file
@@ -0,0 +1,15 @@
+INSERT INTO archived_partners (partnership_id, guild, category, partner_guild, display_name, invite_code, description, emoji, status, removal_reason, removed_at, removed_by, partner_guild_name)
+	SELECT partnership_id, guild, category, partner_guild, display_name, invite_code, description, emoji, 'pending', 'Partnership ended', NOW(), NULL, partner_guild_name
+	FROM partners WHERE status = 'ended';
+INSERT INTO archived_partner_users (partnership_id, user_id)
+	SELECT partner_users.partnership_id, partner_users.user_id
+	FROM partner_users JOIN partners ON partners.partnership_id = partner_users.partnership_id
+	WHERE partners.status = 'ended';
+INSERT INTO archived_partner_self_users (partnership, user_id)
+	SELECT partner_self_users.partnership, partner_self_users.user_id
+	FROM partner_self_users JOIN partners ON partners.partnership_id = partner_self_users.partnership
+	WHERE partners.status = 'ended';
+DELETE FROM partners WHERE status = 'ended';
+UPDATE archived_partners SET status = 'pending' WHERE status = 'ended';
+ALTER TABLE partners DROP CONSTRAINT valid_partner_status;
+ALTER TABLE partners ADD CONSTRAINT valid_partner_status CHECK (status IN ('pending', 'active', 'hiatus'));
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE partners DROP COLUMN member_count;
\ No newline at end of file
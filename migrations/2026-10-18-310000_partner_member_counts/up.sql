@@ -0,0 +1 @@
+ALTER TABLE partners ADD COLUMN member_count INTEGER;
\ No newline at end of file
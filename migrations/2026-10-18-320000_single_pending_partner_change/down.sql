@@ -0,0 +1,3 @@
+ALTER TABLE pending_partner_changes DROP COLUMN review_message;
+ALTER TABLE pending_partner_changes DROP COLUMN review_channel;
+ALTER TABLE pending_partner_changes DROP CONSTRAINT unique_pending_partner_change;
\ No newline at end of file
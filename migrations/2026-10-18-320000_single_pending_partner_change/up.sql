@@ -0,0 +1,5 @@
+DELETE FROM pending_partner_changes older USING pending_partner_changes newer
+	WHERE older.partnership_id = newer.partnership_id AND (older.requested_at, older.id) < (newer.requested_at, newer.id);
+ALTER TABLE pending_partner_changes ADD CONSTRAINT unique_pending_partner_change UNIQUE (partnership_id);
+ALTER TABLE pending_partner_changes ADD COLUMN review_channel BIGINT;
+ALTER TABLE pending_partner_changes ADD COLUMN review_message BIGINT;
\ No newline at end of file
@@ -1,3 +1,4 @@
+pub mod my_partnership;
 pub mod partner_categories;
 pub mod partner_embed;
 pub mod partners;
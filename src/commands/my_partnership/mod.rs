@@ -0,0 +1,85 @@
+use miette::{bail, ensure, Severity};
+use serenity::builder::{CreateCommand, CreateCommandOption};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, CommandOptionType, CommandType, ResolvedValue};
+
+mod review;
+mod set_description;
+mod set_invite;
+mod set_name;
+mod submit;
+
+pub use review::{handle_change_review_button, CHANGE_REVIEW_BUTTON_PREFIX};
+use set_name::MAX_DISPLAY_NAME_LENGTH;
+
+pub fn definition() -> CreateCommand {
+	let set_name_name = CreateCommandOption::new(
+		CommandOptionType::String,
+		"new_display_name",
+		"The name to show for your server",
+	)
+	.required(true)
+	.max_length(MAX_DISPLAY_NAME_LENGTH as u16);
+	let set_name_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_name",
+		"Changes the name shown for your server",
+	)
+	.add_sub_option(set_name_name);
+
+	let set_description_description = CreateCommandOption::new(
+		CommandOptionType::String,
+		"description",
+		"A short, one-line description of your server; leave blank to clear",
+	)
+	.required(false)
+	.max_length(100);
+	let set_description_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_description",
+		"Changes the description shown for your server",
+	)
+	.add_sub_option(set_description_description);
+
+	let set_invite_link = CreateCommandOption::new(
+		CommandOptionType::String,
+		"invite_link",
+		"A new permanent invite link for your server",
+	)
+	.required(true);
+	let set_invite_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_invite",
+		"Changes the invite link listed for your server",
+	)
+	.add_sub_option(set_invite_link);
+
+	// Available to everyone, since representatives usually don't have staff permissions; the subcommands check that the
+	// user represents a partner
+	CreateCommand::new("my_partnership")
+		.kind(CommandType::ChatInput)
+		.dm_permission(false)
+		.description("Manages the listing for a partner server you represent")
+		.add_option(set_description_command)
+		.add_option(set_invite_command)
+		.add_option(set_name_command)
+}
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let options = command.data.options();
+	ensure!(
+		!options.is_empty(),
+		severity = Severity::Error,
+		"called the my_partnership command without subcommands"
+	);
+	let subcommand = options.first().unwrap();
+	let ResolvedValue::SubCommand(subcommand_options) = &subcommand.value else {
+		bail!("Incorrect data type passed for my_partnership subcommand option");
+	};
+	match subcommand.name {
+		"set_description" => set_description::execute(ctx, command, subcommand_options).await,
+		"set_invite" => set_invite::execute(ctx, command, subcommand_options).await,
+		"set_name" => set_name::execute(ctx, command, subcommand_options).await,
+		_ => bail!("Unexpected subcommand for my_partnership command: {:?}", subcommand),
+	}
+}
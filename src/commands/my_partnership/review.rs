@@ -0,0 +1,149 @@
+use crate::database::get_database_connection;
+use crate::models::PendingPartnerChange;
+use crate::schema::{partners, pending_partner_changes};
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::partner_changes::{add_change_fields, apply_partner_change};
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DbError};
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{CreateAllowedMentions, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::ComponentInteraction;
+use serenity::model::permissions::Permissions;
+
+/// The custom ID prefix for the buttons on changes waiting for approval. The action and the change ID follow it,
+/// separated by a colon.
+pub const CHANGE_REVIEW_BUTTON_PREFIX: &str = "partner_change:";
+
+/// Handles the Approve and Reject buttons on a representative's change waiting for approval
+pub async fn handle_change_review_button(ctx: &Context, component: &ComponentInteraction) -> miette::Result<()> {
+	let Some(guild) = component.guild_id else {
+		bail!("Partner change review button was used outside of a guild");
+	};
+	let Some((action, change_id)) = component
+		.data
+		.custom_id
+		.strip_prefix(CHANGE_REVIEW_BUTTON_PREFIX)
+		.and_then(|button| button.split_once(':'))
+	else {
+		bail!(
+			"Unexpected custom ID for partner change review button: {}",
+			component.data.custom_id
+		);
+	};
+
+	let can_manage = component
+		.member
+		.as_ref()
+		.and_then(|member| member.permissions)
+		.is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD));
+	if !can_manage {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You need the Manage Server permission to review partner changes.");
+		component
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let change: Option<(PendingPartnerChange, String)> = {
+		let mut db_connection = db_connection.lock().await;
+		pending_partner_changes::table
+			.inner_join(partners::table)
+			.filter(
+				pending_partner_changes::id
+					.eq(change_id)
+					.and(partners::guild.eq(sql_guild_id)),
+			)
+			.select((pending_partner_changes::all_columns, partners::display_name))
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?
+	};
+	// Changes are removed along with their partner, so a missing change may also mean the partner was removed
+	let Some((change, partner_name)) = change else {
+		let message = CreateInteractionResponseMessage::new()
+			.content("This change is no longer pending.")
+			.components(Vec::new());
+		component
+			.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	};
+
+	let approved = match action {
+		"approve" => true,
+		"reject" => false,
+		_ => bail!("Unexpected partner change review button action: {}", action),
+	};
+
+	let review_result = {
+		let mut db_connection = db_connection.lock().await;
+		db_connection.transaction(|db_connection| {
+			// Deleting the change first makes sure it can only be reviewed once
+			let deleted_count = diesel::delete(pending_partner_changes::table)
+				.filter(pending_partner_changes::id.eq(&change.id))
+				.execute(db_connection)?;
+			if deleted_count == 0 {
+				return Err(DbError::NotFound);
+			}
+			if approved {
+				apply_partner_change(db_connection, &change)?;
+			}
+			Ok(())
+		})
+	};
+	match review_result {
+		Ok(()) => (),
+		Err(DbError::NotFound) => {
+			let message = CreateInteractionResponseMessage::new()
+				.content("This change is no longer pending.")
+				.components(Vec::new());
+			component
+				.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("Another partner already uses the requested name. Rename it or reject this change.");
+			component
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+		Err(error) => bail!(error),
+	}
+
+	let outcome = if approved { "Approved" } else { "Rejected" };
+	let message = CreateInteractionResponseMessage::new()
+		.content(format!("{} by <@{}>.", outcome, component.user.id.get()))
+		.components(Vec::new())
+		.allowed_mentions(CreateAllowedMentions::new());
+	component
+		.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+		.await
+		.into_diagnostic()?;
+
+	if approved {
+		let log_entry = add_change_fields(
+			log_embed(LogKind::Changed, "Partner change approved", Some(component.user.id))
+				.field("Partner", &partner_name, true)
+				.field("Requested by", format!("<@{}>", change.requested_by), true),
+			&change,
+		);
+		send_staff_log(ctx, guild, log_entry).await?;
+		queue_embed_update(ctx, guild).await;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,33 @@
+use super::submit::{submit_change, RequestedChange};
+use miette::bail;
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let mut description = "";
+	for option in options.iter() {
+		match option.name {
+			"description" => {
+				let ResolvedValue::String(value) = option.value else {
+					bail!("Incorrect type provided for description option: {:?}", option.value);
+				};
+				description = value;
+			}
+			_ => bail!(
+				"Invalid option passed to my_partnership set_description command: {}",
+				option.name
+			),
+		}
+	}
+
+	let change = RequestedChange {
+		display_name: None,
+		description: Some(description.trim().to_string()),
+		invite: None,
+	};
+	submit_change(ctx, command, change).await
+}
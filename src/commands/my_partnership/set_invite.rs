@@ -0,0 +1,47 @@
+use super::submit::{submit_change, RequestedChange};
+use crate::utils::invite::resolve_partner_invite;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(invite_option) = options.first() else {
+		bail!("Not enough options passed to my_partnership set_invite command");
+	};
+	ensure!(
+		invite_option.name == "invite_link",
+		severity = Severity::Error,
+		"Wrong option passed to my_partnership set_invite command: {:?}",
+		invite_option
+	);
+	let ResolvedValue::String(invite_link) = invite_option.value else {
+		bail!(
+			"Incorrect type provided for invite_link option: {:?}",
+			invite_option.value
+		);
+	};
+
+	let invite = match resolve_partner_invite(ctx, invite_link).await {
+		Ok(invite) => invite,
+		Err(reason) => {
+			let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reason);
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+	};
+
+	let change = RequestedChange {
+		display_name: None,
+		description: None,
+		invite: Some(invite),
+	};
+	submit_change(ctx, command, change).await
+}
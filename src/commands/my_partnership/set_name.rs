@@ -0,0 +1,59 @@
+use super::submit::{submit_change, RequestedChange};
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+
+/// The longest display name a partner can request, matching the limit on descriptions
+pub(super) const MAX_DISPLAY_NAME_LENGTH: usize = 100;
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(name_option) = options.first() else {
+		bail!("Not enough options passed to my_partnership set_name command");
+	};
+	ensure!(
+		name_option.name == "new_display_name",
+		severity = Severity::Error,
+		"Wrong option passed to my_partnership set_name command: {:?}",
+		name_option
+	);
+	let ResolvedValue::String(new_name) = name_option.value else {
+		bail!(
+			"Incorrect type provided for new_display_name option: {:?}",
+			name_option.value
+		);
+	};
+
+	let new_name = new_name.trim();
+	let invalid_name_message = if new_name.is_empty() {
+		Some(String::from("The name can't be blank."))
+	} else if new_name.chars().count() > MAX_DISPLAY_NAME_LENGTH {
+		Some(format!(
+			"The name can't be longer than {} characters.",
+			MAX_DISPLAY_NAME_LENGTH
+		))
+	} else {
+		None
+	};
+	if let Some(invalid_name_message) = invalid_name_message {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(invalid_name_message);
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let change = RequestedChange {
+		display_name: Some(new_name.to_string()),
+		description: None,
+		invite: None,
+	};
+	submit_change(ctx, command, change).await
+}
@@ -0,0 +1,412 @@
+use super::review::CHANGE_REVIEW_BUTTON_PREFIX;
+use crate::database::get_database_connection;
+use crate::models::{GuildSettings, Partner, PendingPartnerChange};
+use crate::schema::{guild_settings, partner_users, partners, pending_partner_changes};
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::invite::PartnerInvite;
+use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::partner_changes::{add_change_fields, apply_partner_change};
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{deliver_staff_log_with_components, log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DbError};
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+	CreateSelectMenu, CreateSelectMenuKind, EditInteractionResponse, EditMessage,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind,
+};
+use serenity::model::id::{ChannelId, GuildId, MessageId};
+use std::time::{Duration, SystemTime};
+
+/// A change a representative asked to make to their partner's listing
+pub struct RequestedChange {
+	pub display_name: Option<String>,
+	pub description: Option<String>,
+	pub invite: Option<PartnerInvite>,
+}
+
+/// Applies or queues for approval a change to the listing of a partner the user represents, asking which partner if
+/// they represent more than one
+pub async fn submit_change(ctx: &Context, command: &CommandInteraction, change: RequestedChange) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("My partnership command used outside of a guild");
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let sql_user_id = command.user.id.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (settings, represented_partners) = {
+		let mut db_connection = db_connection.lock().await;
+		let settings: Option<GuildSettings> = guild_settings::table
+			.find(sql_guild_id)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let represented_partners: Vec<Partner> = partners::table
+			.filter(
+				partners::guild.eq(sql_guild_id).and(
+					partners::partnership_id.eq_any(
+						partner_users::table
+							.filter(partner_users::user_id.eq(sql_user_id))
+							.select(partner_users::partnership_id),
+					),
+				),
+			)
+			.order(partners::display_name.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		(settings, represented_partners)
+	};
+
+	let Some(settings) = settings else {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP);
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	};
+	if represented_partners.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You don't represent any of this server's partners.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+	if settings.rep_changes_need_approval && settings.log_channel.is_none() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("This server's staff can't review changes right now. Ask them to make the change for you.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let (partner, selection) = if represented_partners.len() == 1 {
+		(&represented_partners[0], None)
+	} else {
+		let Some((partner, selection)) = select_partner(ctx, command, &represented_partners).await? else {
+			return Ok(());
+		};
+		(partner, Some(selection))
+	};
+
+	if let Some(invite) = &change.invite {
+		if invite.guild.id.get() as i64 != partner.partner_guild {
+			let message = CreateInteractionResponseMessage::new().ephemeral(true).content(
+				"That invite is for a different server. Ask staff if your partnership should move to another server.",
+			);
+			respond(ctx, command, selection.as_ref(), message).await?;
+			return Ok(());
+		}
+	}
+
+	let invite = change.invite.filter(|invite| invite.code != partner.invite_code);
+	let pending_change = PendingPartnerChange {
+		id: cuid2::create_id(),
+		partnership_id: partner.partnership_id.clone(),
+		requested_by: sql_user_id,
+		display_name: change.display_name.filter(|name| *name != partner.display_name),
+		description: change
+			.description
+			.filter(|description| *description != partner.description),
+		invite_code: invite.as_ref().map(|invite| invite.code.clone()),
+		partner_guild_name: invite.map(|invite| invite.guild.name),
+		requested_at: SystemTime::now(),
+		review_channel: None,
+		review_message: None,
+	};
+	if pending_change.display_name.is_none()
+		&& pending_change.description.is_none()
+		&& pending_change.invite_code.is_none()
+	{
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("That's already how your server is listed.");
+		respond(ctx, command, selection.as_ref(), message).await?;
+		return Ok(());
+	}
+
+	if settings.rep_changes_need_approval {
+		let message = queue_for_approval(ctx, guild, command, partner, pending_change).await?;
+		respond(ctx, command, selection.as_ref(), message).await?;
+		return Ok(());
+	}
+
+	let update_result = {
+		let mut db_connection = db_connection.lock().await;
+		apply_partner_change(&mut db_connection, &pending_change)
+	};
+	let message = match update_result {
+		Ok(updated_partner) => CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(format!("Updated the listing for {}.", updated_partner.display_name))
+			.allowed_mentions(CreateAllowedMentions::new()),
+		Err(DbError::NotFound) => {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("That server is no longer a partner.");
+			respond(ctx, command, selection.as_ref(), message).await?;
+			return Ok(());
+		}
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("Another partner already uses that name.");
+			respond(ctx, command, selection.as_ref(), message).await?;
+			return Ok(());
+		}
+		Err(error) => bail!(error),
+	};
+	respond(ctx, command, selection.as_ref(), message).await?;
+
+	let log_entry = add_change_fields(
+		log_embed(
+			LogKind::Changed,
+			"Partner changed by representative",
+			Some(command.user.id),
+		)
+		.field("Partner", &partner.display_name, true),
+		&pending_change,
+	);
+	send_staff_log(ctx, guild, log_entry).await?;
+	queue_embed_update(ctx, guild).await;
+
+	Ok(())
+}
+
+/// Stores a change for staff approval and sends the staff log entry for reviewing it. A change still pending for the
+/// same partner is replaced by one combining both, so staff review them together. Returns the response to give the
+/// user.
+async fn queue_for_approval(
+	ctx: &Context,
+	guild: GuildId,
+	command: &CommandInteraction,
+	partner: &Partner,
+	change: PendingPartnerChange,
+) -> miette::Result<CreateInteractionResponseMessage> {
+	let db_connection = get_database_connection(ctx).await;
+	let (change, replaced_change) = {
+		let mut db_connection = db_connection.lock().await;
+		db_connection
+			.transaction(|db_connection| {
+				let replaced_change: Option<PendingPartnerChange> = pending_partner_changes::table
+					.filter(pending_partner_changes::partnership_id.eq(&partner.partnership_id))
+					.first(db_connection)
+					.optional()?;
+				if let Some(replaced_change) = &replaced_change {
+					diesel::delete(pending_partner_changes::table)
+						.filter(pending_partner_changes::id.eq(&replaced_change.id))
+						.execute(db_connection)?;
+				}
+				let change = match &replaced_change {
+					Some(replaced_change) => combine_changes(change, replaced_change),
+					None => change,
+				};
+				diesel::insert_into(pending_partner_changes::table)
+					.values(&change)
+					.execute(db_connection)?;
+				Ok::<_, DbError>((change, replaced_change))
+			})
+			.into_diagnostic()?
+	};
+
+	let log_entry = add_change_fields(
+		log_embed(LogKind::Changed, "Partner change requested", Some(command.user.id)).field(
+			"Partner",
+			&partner.display_name,
+			true,
+		),
+		&change,
+	);
+	let approve_button = CreateButton::new(format!("{}approve:{}", CHANGE_REVIEW_BUTTON_PREFIX, change.id))
+		.label("Approve")
+		.style(ButtonStyle::Success);
+	let reject_button = CreateButton::new(format!("{}reject:{}", CHANGE_REVIEW_BUTTON_PREFIX, change.id))
+		.label("Reject")
+		.style(ButtonStyle::Danger);
+	let components = vec![CreateActionRow::Buttons(vec![approve_button, reject_button])];
+
+	let review_message = match deliver_staff_log_with_components(ctx, guild, log_entry, components).await {
+		Ok(Some(review_message)) => review_message,
+		delivery_result => {
+			if let Err(error) = delivery_result {
+				eprintln!(
+					"Failed to send partner change {} to staff for review: {:?}",
+					change.id, error
+				);
+			}
+
+			// A change staff can't see can't be reviewed, so it's removed, and any change it replaced is put back
+			{
+				let mut db_connection = db_connection.lock().await;
+				db_connection
+					.transaction(|db_connection| {
+						diesel::delete(pending_partner_changes::table)
+							.filter(pending_partner_changes::id.eq(&change.id))
+							.execute(db_connection)?;
+						if let Some(replaced_change) = &replaced_change {
+							diesel::insert_into(pending_partner_changes::table)
+								.values(replaced_change)
+								.execute(db_connection)?;
+						}
+						Ok::<_, DbError>(())
+					})
+					.into_diagnostic()?;
+			}
+			return Ok(CreateInteractionResponseMessage::new().ephemeral(true).content(
+				"Your change couldn't be sent to staff for review, so it wasn't saved. Ask staff to check that the bot can post in the log channel.",
+			));
+		}
+	};
+
+	{
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(pending_partner_changes::table)
+			.filter(pending_partner_changes::id.eq(&change.id))
+			.set((
+				pending_partner_changes::review_channel.eq(review_message.channel_id.get() as i64),
+				pending_partner_changes::review_message.eq(review_message.id.get() as i64),
+			))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?;
+	}
+
+	let Some(replaced_change) = replaced_change else {
+		return Ok(CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("Your change was sent to staff for approval."));
+	};
+	if let (Some(channel_id), Some(message_id)) = (replaced_change.review_channel, replaced_change.review_message) {
+		let card_update = EditMessage::new()
+			.content("Replaced by a newer change request.")
+			.components(Vec::new());
+		// The old entry may have been deleted in the meantime, which doesn't affect the new one
+		let _ = ChannelId::new(channel_id as u64)
+			.edit_message(&ctx.http, MessageId::new(message_id as u64), card_update)
+			.await;
+	}
+	Ok(CreateInteractionResponseMessage::new().ephemeral(true).content(
+		"Your change was combined with the change already waiting for approval for this server and sent to staff.",
+	))
+}
+
+/// Combines a new change with an earlier one still waiting for approval. Parts of the listing the new change includes
+/// replace those in the earlier change.
+fn combine_changes(change: PendingPartnerChange, earlier_change: &PendingPartnerChange) -> PendingPartnerChange {
+	let (invite_code, partner_guild_name) = if change.invite_code.is_some() {
+		(change.invite_code, change.partner_guild_name)
+	} else {
+		(
+			earlier_change.invite_code.clone(),
+			earlier_change.partner_guild_name.clone(),
+		)
+	};
+	PendingPartnerChange {
+		display_name: change.display_name.or_else(|| earlier_change.display_name.clone()),
+		description: change.description.or_else(|| earlier_change.description.clone()),
+		invite_code,
+		partner_guild_name,
+		..change
+	}
+}
+
+/// Asks the user which of the partners they represent to change. Returns the selected partner along with the select
+/// interaction, which must be responded to, or `None` if the user didn't select one.
+async fn select_partner<'a>(
+	ctx: &Context,
+	command: &CommandInteraction,
+	represented_partners: &'a [Partner],
+) -> miette::Result<Option<(&'a Partner, ComponentInteraction)>> {
+	let partner_select_id = cuid2::create_id();
+	let partner_select_row = |page: usize| {
+		let partner_select = CreateSelectMenu::new(
+			&partner_select_id,
+			CreateSelectMenuKind::String {
+				options: get_partners_for_page(represented_partners, page, ""),
+			},
+		)
+		.placeholder("Partner");
+		CreateActionRow::SelectMenu(partner_select)
+	};
+
+	let mut current_partner_page = 0;
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Select the server to change:")
+		.components(vec![partner_select_row(current_partner_page)]);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![partner_select_id.clone()])
+			.timeout(Duration::from_secs(60))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("No changes were made.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(None);
+		};
+		let ComponentInteractionDataKind::StringSelect { values } = &interaction.data.kind else {
+			bail!(
+				"Unexpected interaction type received by my_partnership partner selection: {:?}",
+				interaction.data.kind
+			);
+		};
+		let partner_id = values.first().cloned().unwrap_or_default();
+		if partner_id == "<" || partner_id == ">" {
+			current_partner_page = if partner_id == "<" {
+				current_partner_page.saturating_sub(1)
+			} else {
+				(current_partner_page + 1).min(max_partner_page(represented_partners))
+			};
+			interaction
+				.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+				.await
+				.into_diagnostic()?;
+			let message = EditInteractionResponse::new().components(vec![partner_select_row(current_partner_page)]);
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			continue;
+		}
+		let Some(partner) = represented_partners
+			.iter()
+			.find(|partner| partner.partnership_id == partner_id)
+		else {
+			bail!("Unknown partner selected for my_partnership: {}", partner_id);
+		};
+
+		return Ok(Some((partner, interaction)));
+	}
+}
+
+/// Responds to the partner selection if there was one, or to the command otherwise
+async fn respond(
+	ctx: &Context,
+	command: &CommandInteraction,
+	selection: Option<&ComponentInteraction>,
+	message: CreateInteractionResponseMessage,
+) -> miette::Result<()> {
+	let response = CreateInteractionResponse::Message(message);
+	match selection {
+		Some(interaction) => interaction.create_response(&ctx.http, response).await,
+		None => command.create_response(&ctx.http, response).await,
+	}
+	.into_diagnostic()
+}
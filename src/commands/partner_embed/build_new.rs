@@ -1,22 +1,23 @@
+use crate::commands::partner_embed::preview::{content_modal, edit_with_preview};
 use crate::database::get_database_connection;
 use crate::models::{EmbedData, PartnerCategory};
 use crate::schema::{embed_data, partner_categories};
-use crate::sync::embed::update_embed;
+use crate::sync::embed::DEFAULT_PARTNER_LINE_TEMPLATE;
+use crate::sync::embed_queue::queue_embed_update;
 use crate::utils::setup_check::guild_setup_check_with_reply;
-use diesel::dsl::max;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use diesel::result::{DatabaseErrorKind, Error as DbError};
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
-	CreateActionRow, CreateButton, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
-	CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
+	CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu,
+	CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
 };
 use serenity::client::Context;
 use serenity::collector::ComponentInteractionCollector;
 use serenity::model::application::{
-	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, InputTextStyle,
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind,
 };
-use serenity::utils::CreateQuickModal;
 use std::time::Duration;
 
 pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
@@ -131,25 +132,32 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		(category_id, Some(category_interaction))
 	};
 
-	let name_input = CreateInputText::new(InputTextStyle::Short, "Embed Name", "")
-		.placeholder("Internal name for the embed; used for reference later")
-		.max_length(100)
-		.required(true);
-	let embed_text_input = CreateInputText::new(InputTextStyle::Paragraph, "Text", "")
-		.max_length(4000)
-		.required(false);
-	let image_url_input = CreateInputText::new(InputTextStyle::Short, "Image URL", "").required(false);
-	let color_input = CreateInputText::new(InputTextStyle::Short, "Color", "")
-		.min_length(6)
-		.max_length(6)
-		.required(false);
-
-	let modal = CreateQuickModal::new("Create New Embed")
-		.timeout(Duration::from_secs(900))
-		.field(name_input)
-		.field(embed_text_input)
-		.field(image_url_input)
-		.field(color_input);
+	let mut pending_embed = EmbedData {
+		id: cuid2::create_id(),
+		guild: sql_guild_id,
+		embed_part_sequence_number: 0,
+		embed_name: String::new(),
+		partner_category_list: if partner_category.is_empty() {
+			None
+		} else {
+			Some(partner_category)
+		},
+		embed_text: String::new(),
+		image_url: String::new(),
+		color: None,
+		channel: None,
+		title: String::new(),
+		title_url: String::new(),
+		thumbnail_url: String::new(),
+		author_name: String::new(),
+		author_icon_url: String::new(),
+		footer_text: String::new(),
+		footer_icon_url: String::new(),
+		show_timestamp: false,
+		partner_line_template: DEFAULT_PARTNER_LINE_TEMPLATE.to_string(),
+	};
+
+	let modal = content_modal("Create New Embed", &pending_embed);
 	let modal_response = if let Some(interaction) = category_interaction {
 		interaction.quick_modal(ctx, modal).await
 	} else {
@@ -160,59 +168,33 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 	let Some(modal_response) = modal_response else {
 		return Ok(());
 	};
-	let (modal_interaction, modal_inputs) = (modal_response.interaction, modal_response.inputs);
-	let mut inputs_iter = modal_inputs.into_iter();
-	let Some(name) = inputs_iter.next() else {
-		bail!("Required embed name was not entered");
-	};
-	let embed_text = inputs_iter.next().unwrap_or_default();
-	let image_url = inputs_iter.next().unwrap_or_default();
-	let color = inputs_iter.next().unwrap_or_default();
-
-	let color: Option<i32> = if color.is_empty() {
-		None
-	} else {
-		match i32::from_str_radix(&color, 16) {
-			Ok(color) => Some(color),
-			Err(_) => {
-				let message = CreateInteractionResponseMessage::new()
-					.ephemeral(true)
-					.content("The entered color is invalid.");
-				modal_interaction
-					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
-					.await
-					.into_diagnostic()?;
-				return Ok(());
-			}
-		}
+	let Some(publish_interaction) = edit_with_preview(
+		ctx,
+		modal_response,
+		guild,
+		&mut pending_embed,
+		"Create New Embed",
+		"Embed was not created.",
+	)
+	.await?
+	else {
+		return Ok(());
 	};
+	let name = pending_embed.embed_name.clone();
 
-	{
+	let embed_created = {
 		let mut db_connection = db_connection.lock().await;
 		let last_embed_number: Option<i32> = embed_data::table
 			.filter(embed_data::guild.eq(sql_guild_id))
-			.select(max(embed_data::embed_part_sequence_number))
+			.select(diesel::dsl::max(embed_data::embed_part_sequence_number))
 			.first(&mut *db_connection)
 			.into_diagnostic()?;
-		let next_embed_number = last_embed_number.unwrap_or(0) + 1;
-		let embed_data = EmbedData {
-			id: cuid2::create_id(),
-			guild: sql_guild_id,
-			embed_part_sequence_number: next_embed_number,
-			embed_name: name.clone(),
-			partner_category_list: if partner_category.is_empty() {
-				None
-			} else {
-				Some(partner_category)
-			},
-			embed_text,
-			image_url,
-			color,
-		};
+		pending_embed.embed_part_sequence_number = last_embed_number.unwrap_or(0) + 1;
 
 		let insert_result = diesel::insert_into(embed_data::table)
-			.values(embed_data)
+			.values(pending_embed)
 			.execute(&mut *db_connection);
+		let embed_created = insert_result.is_ok();
 		let message = match insert_result {
 			Ok(_) => CreateInteractionResponseMessage::new().content(format!("Successfully added new embed: {}", name)),
 			Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, violation_info)) => {
@@ -229,13 +211,19 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 			}
 			Err(error) => bail!(error),
 		};
-		modal_interaction
+		publish_interaction
 			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
 			.await
 			.into_diagnostic()?;
-	}
 
-	update_embed(ctx, guild).await?;
+		embed_created
+	};
+
+	if embed_created {
+		let embed = log_embed(LogKind::Added, "Embed created", Some(command.user.id)).field("Embed", name, true);
+		send_staff_log(ctx, guild, embed).await?;
+		queue_embed_update(ctx, guild).await;
+	}
 
 	Ok(())
 }
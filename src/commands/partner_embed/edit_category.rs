@@ -1,8 +1,9 @@
 use crate::database::get_database_connection;
 use crate::models::{EmbedData, PartnerCategory};
 use crate::schema::{embed_data, partner_categories};
-use crate::sync::embed::update_embed;
+use crate::sync::embed_queue::queue_embed_update;
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
@@ -203,7 +204,10 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 				"The embed {} was updated to display the category {}.",
 				embed.embed_name, category.name
 			),
-			None => String::from("The embed {} was updated to remove the partner category display."),
+			None => format!(
+				"The embed {} was updated to remove the partner category display.",
+				embed.embed_name
+			),
 		};
 		let message = CreateInteractionResponseMessage::new().content(message_content);
 		interaction
@@ -212,7 +216,22 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 			.into_diagnostic()?;
 	}
 
-	update_embed(ctx, guild).await?;
+	let old_category_name = embed
+		.partner_category_list
+		.as_ref()
+		.and_then(|old_category_id| categories.iter().find(|category| category.id == *old_category_id))
+		.map(|category| category.name.clone())
+		.unwrap_or_else(|| String::from("None"));
+	let new_category_name = category
+		.map(|category| category.name.clone())
+		.unwrap_or_else(|| String::from("None"));
+	let log_entry = log_embed(LogKind::Changed, "Embed category changed", Some(command.user.id))
+		.field("Embed", &embed.embed_name, true)
+		.field("Old category", old_category_name, true)
+		.field("New category", new_category_name, true);
+	send_staff_log(ctx, guild, log_entry).await?;
+
+	queue_embed_update(ctx, guild).await;
 
 	Ok(())
 }
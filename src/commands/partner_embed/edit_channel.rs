@@ -0,0 +1,232 @@
+use crate::database::get_database_connection;
+use crate::models::EmbedData;
+use crate::schema::embed_data;
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{
+	CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu,
+	CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, ResolvedOption, ResolvedValue,
+};
+use std::time::Duration;
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partner embed command was run outside of a guild");
+	};
+
+	let channel = match options.first() {
+		Some(channel_option) => {
+			ensure!(
+				channel_option.name == "channel",
+				severity = Severity::Error,
+				"wrong option received by partner_embed edit_channel command"
+			);
+			let ResolvedValue::Channel(channel) = channel_option.value else {
+				bail!("Channel option got a non-channel value: {:?}", channel_option);
+			};
+			let channel = channel.id.to_channel(&ctx.http).await.into_diagnostic()?;
+			let Some(channel) = channel.guild() else {
+				bail!("non-guild channel passed as an embed channel");
+			};
+			Some(channel)
+		}
+		None => None,
+	};
+
+	if let Some(channel) = &channel {
+		if channel.guild_id != guild {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("The provided channel isn't in this server.");
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let embeds: Vec<EmbedData> = {
+		let mut db_connection = db_connection.lock().await;
+		if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+			return Ok(());
+		}
+
+		embed_data::table
+			.filter(embed_data::guild.eq(sql_guild_id))
+			.order(embed_data::embed_part_sequence_number.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	if embeds.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("There are no embeds to edit.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let embed_select_options: Vec<CreateSelectMenuOption> = embeds
+		.iter()
+		.map(|embed| CreateSelectMenuOption::new(&embed.embed_name, &embed.id))
+		.collect();
+
+	let embed_select_id = cuid2::create_id();
+	let submit_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+
+	let embed_select = CreateSelectMenu::new(
+		&embed_select_id,
+		CreateSelectMenuKind::String {
+			options: embed_select_options,
+		},
+	)
+	.placeholder("Embed");
+	let submit_button = CreateButton::new(&submit_button_id)
+		.label("Submit")
+		.style(ButtonStyle::Primary);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+
+	let embed_row = CreateActionRow::SelectMenu(embed_select);
+	let buttons_row = CreateActionRow::Buttons(vec![submit_button, cancel_button]);
+
+	let message_content = match &channel {
+		Some(channel) => format!("Select the embed to publish to <#{}>:", channel.id.get()),
+		None => String::from("Select the embed to publish to the default embed channel:"),
+	};
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(message_content)
+		.components(vec![embed_row, buttons_row]);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let mut embed_id = String::new();
+
+	let interaction: ComponentInteraction = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![
+				embed_select_id.clone(),
+				submit_button_id.clone(),
+				cancel_button_id.clone(),
+			])
+			.timeout(Duration::from_secs(60))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("No embed was modified.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				let value = values.first().cloned().unwrap_or_default();
+				if interaction.data.custom_id == embed_select_id {
+					embed_id = value;
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+						.await
+						.into_diagnostic()?;
+				}
+			}
+			ComponentInteractionDataKind::Button => {
+				if interaction.data.custom_id == submit_button_id {
+					break interaction;
+				}
+				if interaction.data.custom_id == cancel_button_id {
+					let message = CreateInteractionResponseMessage::new()
+						.ephemeral(true)
+						.content("No embed was modified.");
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+						.await
+						.into_diagnostic()?;
+					return Ok(());
+				}
+			}
+			_ => bail!(
+				"Unexpected interaction type received by the partner_embed edit_channel command: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	if embed_id.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("No embed was modified; an embed was not selected.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let Some(embed) = embeds.iter().find(|embed| embed.id == embed_id) else {
+		bail!("Embed selections desynchronized with embed list");
+	};
+
+	let sql_channel_id = channel.as_ref().map(|channel| channel.id.get() as i64);
+	{
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(embed_data::table)
+			.filter(embed_data::id.eq(&embed.id))
+			.set(embed_data::channel.eq(sql_channel_id))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?;
+	}
+
+	let channel_description = match &channel {
+		Some(channel) => format!("<#{}>", channel.id.get()),
+		None => String::from("the default embed channel"),
+	};
+	let message = CreateInteractionResponseMessage::new().content(format!(
+		"The embed {} will now be published to {}.",
+		embed.embed_name, channel_description
+	));
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let old_channel_description = match embed.channel {
+		Some(channel_id) => format!("<#{}>", channel_id as u64),
+		None => String::from("Default"),
+	};
+	let new_channel_description = match &channel {
+		Some(channel) => format!("<#{}>", channel.id.get()),
+		None => String::from("Default"),
+	};
+	let log_entry = log_embed(LogKind::Changed, "Embed channel changed", Some(command.user.id))
+		.field("Embed", &embed.embed_name, true)
+		.field("Old channel", old_channel_description, true)
+		.field("New channel", new_channel_description, true);
+	send_staff_log(ctx, guild, log_entry).await?;
+
+	queue_embed_update(ctx, guild).await;
+
+	Ok(())
+}
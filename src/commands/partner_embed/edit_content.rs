@@ -1,21 +1,22 @@
+use crate::commands::partner_embed::preview::{content_modal, edit_with_preview};
 use crate::database::get_database_connection;
 use crate::models::EmbedData;
 use crate::schema::embed_data;
-use crate::sync::embed::update_embed;
+use crate::sync::embed_queue::queue_embed_update;
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use diesel::result::{DatabaseErrorKind, Error as DbError};
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
-	CreateActionRow, CreateButton, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
-	CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
+	CreateActionRow, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage, CreateSelectMenu,
+	CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
 };
 use serenity::client::Context;
 use serenity::collector::ComponentInteractionCollector;
 use serenity::model::application::{
-	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, InputTextStyle,
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind,
 };
-use serenity::utils::CreateQuickModal;
 use std::time::Duration;
 
 pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
@@ -128,32 +129,8 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		bail!("Embed selection desynchronized with the available embed list");
 	};
 
-	let embed_name_input = CreateInputText::new(InputTextStyle::Short, "Embed Name", "")
-		.required(true)
-		.max_length(100)
-		.value(&embed.embed_name);
-	let embed_text_input = CreateInputText::new(InputTextStyle::Paragraph, "Text", "")
-		.required(false)
-		.max_length(4000)
-		.value(&embed.embed_text);
-	let image_url_input = CreateInputText::new(InputTextStyle::Short, "Image URL", "")
-		.required(false)
-		.value(&embed.image_url);
-	let mut color_input = CreateInputText::new(InputTextStyle::Short, "Color", "")
-		.required(false)
-		.min_length(6)
-		.max_length(6);
-
-	if let Some(color) = embed.color {
-		color_input = color_input.value(format!("{:06x}", color));
-	}
-
-	let modal = CreateQuickModal::new("Edit Embed")
-		.field(embed_name_input)
-		.field(embed_text_input)
-		.field(image_url_input)
-		.field(color_input)
-		.timeout(Duration::from_secs(600));
+	let mut pending_embed = embed.clone();
+	let modal = content_modal("Edit Embed", &pending_embed);
 	let modal_response = interaction.quick_modal(ctx, modal).await.into_diagnostic()?;
 
 	let Some(modal_response) = modal_response else {
@@ -164,49 +141,49 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		return Ok(());
 	};
 
-	let modal_inputs = modal_response.inputs;
-	let (new_embed_name, new_embed_text, new_image_url, new_color) =
-		(&modal_inputs[0], &modal_inputs[1], &modal_inputs[2], &modal_inputs[3]);
-
-	let new_color = if new_color.is_empty() {
-		None
-	} else {
-		match i32::from_str_radix(new_color, 16) {
-			Ok(color) => Some(color),
-			Err(_) => {
-				let message = CreateInteractionResponseMessage::new()
-					.ephemeral(true)
-					.content("The entered color was invalid.");
-				modal_response
-					.interaction
-					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
-					.await
-					.into_diagnostic()?;
-				return Ok(());
-			}
-		}
+	let Some(publish_interaction) = edit_with_preview(
+		ctx,
+		modal_response,
+		guild_id,
+		&mut pending_embed,
+		"Edit Embed",
+		"Canceled embed edit.",
+	)
+	.await?
+	else {
+		return Ok(());
 	};
+	let new_embed_name = &pending_embed.embed_name;
 
-	{
+	let embed_updated = {
 		let mut db_connection = db_connection.lock().await;
 		let update_result = diesel::update(embed_data::table)
 			.filter(embed_data::id.eq(&embed.id))
 			.set((
 				embed_data::embed_name.eq(new_embed_name),
-				embed_data::embed_text.eq(new_embed_text),
-				embed_data::image_url.eq(new_image_url),
-				embed_data::color.eq(new_color),
+				embed_data::embed_text.eq(&pending_embed.embed_text),
+				embed_data::image_url.eq(&pending_embed.image_url),
+				embed_data::color.eq(pending_embed.color),
+				embed_data::title.eq(&pending_embed.title),
+				embed_data::title_url.eq(&pending_embed.title_url),
+				embed_data::thumbnail_url.eq(&pending_embed.thumbnail_url),
+				embed_data::author_name.eq(&pending_embed.author_name),
+				embed_data::author_icon_url.eq(&pending_embed.author_icon_url),
+				embed_data::footer_text.eq(&pending_embed.footer_text),
+				embed_data::footer_icon_url.eq(&pending_embed.footer_icon_url),
+				embed_data::show_timestamp.eq(pending_embed.show_timestamp),
+				embed_data::partner_line_template.eq(&pending_embed.partner_line_template),
 			))
 			.execute(&mut *db_connection);
 		match update_result {
 			Ok(_) => {
 				let message = CreateInteractionResponseMessage::new()
 					.content(format!("Successfully updated the embed {}.", new_embed_name));
-				modal_response
-					.interaction
+				publish_interaction
 					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
 					.await
 					.into_diagnostic()?;
+				true
 			}
 			Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, violation_info)) => {
 				let message_content = if violation_info.constraint_name() == Some("unique_embed_name_per_guild") {
@@ -217,17 +194,31 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 				let message = CreateInteractionResponseMessage::new()
 					.ephemeral(true)
 					.content(message_content);
-				modal_response
-					.interaction
+				publish_interaction
 					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
 					.await
 					.into_diagnostic()?;
+				false
 			}
 			Err(error) => bail!(error),
 		}
+	};
+
+	if embed_updated {
+		let mut log_entry = log_embed(LogKind::Changed, "Embed content edited", Some(command.user.id)).field(
+			"Embed",
+			new_embed_name,
+			true,
+		);
+		if *new_embed_name != embed.embed_name {
+			log_entry = log_entry.field("Previous name", &embed.embed_name, true);
+		}
+		send_staff_log(ctx, guild_id, log_entry).await?;
 	}
 
-	update_embed(ctx, guild_id).await?;
+	if embed_updated {
+		queue_embed_update(ctx, guild_id).await;
+	}
 
 	Ok(())
 }
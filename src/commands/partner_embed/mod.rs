@@ -1,14 +1,19 @@
 use miette::{bail, ensure, Severity};
 use serenity::builder::{CreateCommand, CreateCommandOption};
 use serenity::client::Context;
-use serenity::model::application::{CommandInteraction, CommandOptionType, CommandType};
+use serenity::model::application::{CommandInteraction, CommandOptionType, CommandType, ResolvedValue};
+use serenity::model::channel::ChannelType;
 use serenity::model::permissions::Permissions;
 
 mod build_new;
 mod edit_category;
+mod edit_channel;
 mod edit_content;
+mod preview;
+mod publish;
 mod remove;
 mod reorder;
+mod status;
 
 pub fn definition() -> CreateCommand {
 	let build_new_subcommand = CreateCommandOption::new(
@@ -21,11 +26,35 @@ pub fn definition() -> CreateCommand {
 		"edit_category",
 		"Edits the partner category for an embed",
 	);
+	let edit_channel_subcommand = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"edit_channel",
+		"Edits the channel to which an embed is published",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Channel,
+			"channel",
+			"The channel in which to publish the embed; leave blank to use the default embed channel",
+		)
+		.required(false)
+		.channel_types(vec![ChannelType::Text, ChannelType::News, ChannelType::Forum]),
+	);
 	let edit_content_subcommand = CreateCommandOption::new(
 		CommandOptionType::SubCommand,
 		"edit_content",
 		"Edits the content of an embed",
 	);
+	let publish_subcommand = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"publish",
+		"Publishes all pending changes to the partner list",
+	);
+	let status_subcommand = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"status",
+		"Shows changes to the partner list that haven't been published",
+	);
 	let reorder_subcommand = CreateCommandOption::new(CommandOptionType::SubCommand, "reorder", "Reorders embeds");
 	let remove_subcommand = CreateCommandOption::new(CommandOptionType::SubCommand, "remove", "Removes an embed");
 
@@ -36,9 +65,12 @@ pub fn definition() -> CreateCommand {
 		.description("Manage the embed listing partners")
 		.add_option(build_new_subcommand)
 		.add_option(edit_category_subcommand)
+		.add_option(edit_channel_subcommand)
 		.add_option(edit_content_subcommand)
+		.add_option(publish_subcommand)
 		.add_option(reorder_subcommand)
 		.add_option(remove_subcommand)
+		.add_option(status_subcommand)
 }
 
 pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
@@ -52,9 +84,17 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 	match subcommand.name {
 		"build_new" => build_new::execute(ctx, command).await,
 		"edit_category" => edit_category::execute(ctx, command).await,
+		"edit_channel" => {
+			let ResolvedValue::SubCommand(subcommand_options) = &subcommand.value else {
+				bail!("Incorrect data type passed for partner_embed edit_channel subcommand");
+			};
+			edit_channel::execute(ctx, command, subcommand_options).await
+		}
 		"edit_content" => edit_content::execute(ctx, command).await,
+		"publish" => publish::execute(ctx, command).await,
 		"reorder" => reorder::execute(ctx, command).await,
 		"remove" => remove::execute(ctx, command).await,
+		"status" => status::execute(ctx, command).await,
 		_ => bail!(
 			"Unexpected subcommand passed to the partner_embed command: {:?}",
 			subcommand
@@ -0,0 +1,522 @@
+use crate::database::get_database_connection;
+use crate::models::EmbedData;
+use crate::schema::embed_data;
+use crate::sync::embed::{build_channel_messages, DEFAULT_PARTNER_LINE_TEMPLATE, PARTNER_LINE_PLACEHOLDERS};
+use diesel::prelude::*;
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{
+	CreateActionRow, CreateButton, CreateInputText, CreateInteractionResponse, CreateInteractionResponseFollowup,
+	CreateInteractionResponseMessage, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, ComponentInteraction, ComponentInteractionDataKind, InputTextStyle, ModalInteraction,
+};
+use serenity::model::id::{GuildId, MessageId};
+use serenity::utils::{CreateQuickModal, QuickModalResponse};
+use std::time::Duration;
+
+/// Discord's limit on the length of an embed's author name
+const MAX_AUTHOR_NAME_LENGTH: usize = 256;
+
+/// Discord's limit on the length of an embed's footer text
+const MAX_FOOTER_TEXT_LENGTH: usize = 2048;
+
+/// The action chosen by the user for a previewed embed change
+enum PreviewChoice {
+	Publish(ComponentInteraction),
+	EditContent(ComponentInteraction),
+	EditDetails(ComponentInteraction),
+	EditAuthorFooter(ComponentInteraction),
+	Discard(ComponentInteraction),
+	TimedOut,
+}
+
+/// Which page of embed inputs a modal contains; Discord limits modals to five inputs, so embeds are edited across
+/// several
+#[derive(Clone, Copy)]
+enum ModalPage {
+	Content,
+	Details,
+	AuthorFooter,
+}
+
+/// The IDs of the buttons attached to a preview
+struct PreviewButtonIds {
+	publish: String,
+	edit_content: String,
+	edit_details: String,
+	edit_author_footer: String,
+	timestamp: String,
+	discard: String,
+}
+
+impl PreviewButtonIds {
+	fn new() -> Self {
+		Self {
+			publish: cuid2::create_id(),
+			edit_content: cuid2::create_id(),
+			edit_details: cuid2::create_id(),
+			edit_author_footer: cuid2::create_id(),
+			timestamp: cuid2::create_id(),
+			discard: cuid2::create_id(),
+		}
+	}
+
+	fn all(&self) -> Vec<String> {
+		vec![
+			self.publish.clone(),
+			self.edit_content.clone(),
+			self.edit_details.clone(),
+			self.edit_author_footer.clone(),
+			self.timestamp.clone(),
+			self.discard.clone(),
+		]
+	}
+
+	/// Creates the rows of buttons for the preview; editing buttons go on the first row, since Discord limits rows to
+	/// five buttons
+	fn button_rows(&self, show_timestamp: bool) -> Vec<CreateActionRow> {
+		let publish_button = CreateButton::new(&self.publish)
+			.label("Publish")
+			.style(ButtonStyle::Primary);
+		let edit_content_button = CreateButton::new(&self.edit_content)
+			.label("Edit content")
+			.style(ButtonStyle::Secondary);
+		let edit_details_button = CreateButton::new(&self.edit_details)
+			.label("Edit details")
+			.style(ButtonStyle::Secondary);
+		let edit_author_footer_button = CreateButton::new(&self.edit_author_footer)
+			.label("Edit author & footer")
+			.style(ButtonStyle::Secondary);
+		let timestamp_button = CreateButton::new(&self.timestamp)
+			.label(if show_timestamp {
+				"Hide timestamp"
+			} else {
+				"Show timestamp"
+			})
+			.style(ButtonStyle::Secondary);
+		let discard_button = CreateButton::new(&self.discard)
+			.label("Discard")
+			.style(ButtonStyle::Danger);
+		vec![
+			CreateActionRow::Buttons(vec![
+				edit_content_button,
+				edit_details_button,
+				edit_author_footer_button,
+				timestamp_button,
+			]),
+			CreateActionRow::Buttons(vec![publish_button, discard_button]),
+		]
+	}
+}
+
+/// Sets the initial value of an input if there's a value to set
+fn prefilled(input: CreateInputText, value: &str) -> CreateInputText {
+	if value.is_empty() {
+		input
+	} else {
+		input.value(value)
+	}
+}
+
+/// Creates the modal for editing the content of an embed, prefilled with the embed's current content
+pub fn content_modal(title: &str, embed: &EmbedData) -> CreateQuickModal {
+	let name_input = CreateInputText::new(InputTextStyle::Short, "Embed Name", "")
+		.placeholder("Internal name for the embed; used for reference later")
+		.max_length(100)
+		.required(true);
+	let title_input = CreateInputText::new(InputTextStyle::Short, "Title", "")
+		.max_length(256)
+		.required(false);
+	let embed_text_input = CreateInputText::new(InputTextStyle::Paragraph, "Text", "")
+		.max_length(4000)
+		.required(false);
+	let image_url_input = CreateInputText::new(InputTextStyle::Short, "Image URL", "").required(false);
+	let mut color_input = CreateInputText::new(InputTextStyle::Short, "Color", "")
+		.min_length(6)
+		.max_length(6)
+		.required(false);
+	if let Some(color) = embed.color {
+		color_input = color_input.value(format!("{:06x}", color));
+	}
+
+	CreateQuickModal::new(title)
+		.timeout(Duration::from_secs(900))
+		.field(prefilled(name_input, &embed.embed_name))
+		.field(prefilled(title_input, &embed.title))
+		.field(prefilled(embed_text_input, &embed.embed_text))
+		.field(prefilled(image_url_input, &embed.image_url))
+		.field(color_input)
+}
+
+/// Creates the modal for editing the title link, thumbnail, and partner line template of an embed
+fn details_modal(title: &str, embed: &EmbedData) -> CreateQuickModal {
+	let title_url_input = CreateInputText::new(InputTextStyle::Short, "Title Link URL", "")
+		.placeholder("Only shown if the embed has a title")
+		.required(false);
+	let thumbnail_url_input = CreateInputText::new(InputTextStyle::Short, "Thumbnail URL", "").required(false);
+	let partner_line_input = CreateInputText::new(InputTextStyle::Short, "Partner Line Template", "")
+		.placeholder(PARTNER_LINE_PLACEHOLDERS)
+		.max_length(500)
+		.required(false)
+		.value(&embed.partner_line_template);
+
+	CreateQuickModal::new(title)
+		.timeout(Duration::from_secs(900))
+		.field(prefilled(title_url_input, &embed.title_url))
+		.field(prefilled(thumbnail_url_input, &embed.thumbnail_url))
+		.field(partner_line_input)
+}
+
+/// Creates the modal for editing the author and footer of an embed
+fn author_footer_modal(title: &str, embed: &EmbedData) -> CreateQuickModal {
+	let author_name_input = CreateInputText::new(InputTextStyle::Short, "Author Name", "")
+		.max_length(MAX_AUTHOR_NAME_LENGTH as u16)
+		.required(false);
+	let author_icon_url_input = CreateInputText::new(InputTextStyle::Short, "Author Icon URL", "")
+		.placeholder("Only shown if the embed has an author name")
+		.required(false);
+	let footer_text_input = CreateInputText::new(InputTextStyle::Paragraph, "Footer Text", "")
+		.max_length(MAX_FOOTER_TEXT_LENGTH as u16)
+		.required(false);
+	let footer_icon_url_input = CreateInputText::new(InputTextStyle::Short, "Footer Icon URL", "")
+		.placeholder("Only shown if the embed has footer text")
+		.required(false);
+
+	CreateQuickModal::new(title)
+		.timeout(Duration::from_secs(900))
+		.field(prefilled(author_name_input, &embed.author_name))
+		.field(prefilled(author_icon_url_input, &embed.author_icon_url))
+		.field(prefilled(footer_text_input, &embed.footer_text))
+		.field(prefilled(footer_icon_url_input, &embed.footer_icon_url))
+}
+
+/// Checks that a URL input is either empty or a web link, which is all Discord accepts for embed links and images
+fn is_valid_url(url: &str) -> bool {
+	url.is_empty() || url.starts_with("https://") || url.starts_with("http://")
+}
+
+/// Applies the inputs from a modal page to the embed. If the inputs are invalid, returns the message to show to the
+/// user instead, leaving the embed unchanged.
+fn apply_inputs(embed: &mut EmbedData, page: ModalPage, inputs: Vec<String>) -> Result<(), &'static str> {
+	let mut inputs_iter = inputs.into_iter();
+	match page {
+		ModalPage::Content => {
+			let name = inputs_iter.next().unwrap_or_default();
+			let title = inputs_iter.next().unwrap_or_default();
+			let embed_text = inputs_iter.next().unwrap_or_default();
+			let image_url = inputs_iter.next().unwrap_or_default().trim().to_string();
+			let color = inputs_iter.next().unwrap_or_default();
+
+			if !is_valid_url(&image_url) {
+				return Err("The image URL must start with https:// or http://.");
+			}
+
+			let color = if color.is_empty() {
+				None
+			} else {
+				match i32::from_str_radix(&color, 16) {
+					Ok(color) => Some(color),
+					Err(_) => return Err("The entered color is invalid."),
+				}
+			};
+
+			embed.embed_name = name;
+			embed.title = title;
+			embed.embed_text = embed_text;
+			embed.image_url = image_url;
+			embed.color = color;
+		}
+		ModalPage::Details => {
+			let title_url = inputs_iter.next().unwrap_or_default().trim().to_string();
+			let thumbnail_url = inputs_iter.next().unwrap_or_default().trim().to_string();
+			let partner_line_template = inputs_iter.next().unwrap_or_default();
+
+			if !is_valid_url(&title_url) {
+				return Err("The title link URL must start with https:// or http://.");
+			}
+			if !is_valid_url(&thumbnail_url) {
+				return Err("The thumbnail URL must start with https:// or http://.");
+			}
+
+			embed.title_url = title_url;
+			embed.thumbnail_url = thumbnail_url;
+			embed.partner_line_template = if partner_line_template.is_empty() {
+				DEFAULT_PARTNER_LINE_TEMPLATE.to_string()
+			} else {
+				partner_line_template
+			};
+		}
+		ModalPage::AuthorFooter => {
+			let author_name = inputs_iter.next().unwrap_or_default().trim().to_string();
+			let author_icon_url = inputs_iter.next().unwrap_or_default().trim().to_string();
+			let footer_text = inputs_iter.next().unwrap_or_default().trim().to_string();
+			let footer_icon_url = inputs_iter.next().unwrap_or_default().trim().to_string();
+
+			if author_name.chars().count() > MAX_AUTHOR_NAME_LENGTH {
+				return Err("The author name can't be longer than 256 characters.");
+			}
+			if footer_text.chars().count() > MAX_FOOTER_TEXT_LENGTH {
+				return Err("The footer text can't be longer than 2048 characters.");
+			}
+			if !is_valid_url(&author_icon_url) {
+				return Err("The author icon URL must start with https:// or http://.");
+			}
+			if !is_valid_url(&footer_icon_url) {
+				return Err("The footer icon URL must start with https:// or http://.");
+			}
+			if author_name.is_empty() && !author_icon_url.is_empty() {
+				return Err("An author icon can only be shown with an author name.");
+			}
+			if footer_text.is_empty() && !footer_icon_url.is_empty() {
+				return Err("A footer icon can only be shown with footer text.");
+			}
+
+			embed.author_name = author_name;
+			embed.author_icon_url = author_icon_url;
+			embed.footer_text = footer_text;
+			embed.footer_icon_url = footer_icon_url;
+		}
+	}
+	Ok(())
+}
+
+/// Checks whether the embed's name is already used by a different embed in the guild
+async fn embed_name_in_use(ctx: &Context, embed: &EmbedData) -> miette::Result<bool> {
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+	let existing_count: i64 = embed_data::table
+		.filter(
+			embed_data::guild
+				.eq(embed.guild)
+				.and(embed_data::embed_name.eq(&embed.embed_name))
+				.and(embed_data::id.ne(&embed.id)),
+		)
+		.count()
+		.get_result(&mut *db_connection)
+		.into_diagnostic()?;
+	Ok(existing_count > 0)
+}
+
+/// Applies the submitted content modal to the pending embed, then previews the result until the user publishes or
+/// abandons the change, allowing each page of the embed to be edited in between. If later edits are invalid, the error
+/// is shown with the preview of the last valid version. Returns the interaction from the publish button, which the
+/// caller must respond to after persisting the embed, or `None` if the change was abandoned. Nothing is persisted here.
+pub async fn edit_with_preview(
+	ctx: &Context,
+	modal_response: QuickModalResponse,
+	guild: GuildId,
+	pending_embed: &mut EmbedData,
+	modal_title: &str,
+	discard_message: &str,
+) -> miette::Result<Option<ComponentInteraction>> {
+	let mut modal_response = modal_response;
+	let mut modal_page = ModalPage::Content;
+	let mut first_pass = true;
+	loop {
+		let modal_interaction = modal_response.interaction;
+		let mut edited_embed = pending_embed.clone();
+		let error_message = match apply_inputs(&mut edited_embed, modal_page, modal_response.inputs) {
+			Err(error_message) => Some(error_message.to_string()),
+			Ok(()) if embed_name_in_use(ctx, &edited_embed).await? => Some(format!(
+				"The embed name {} is already in use for another embed.",
+				edited_embed.embed_name
+			)),
+			Ok(()) => None,
+		};
+		match error_message {
+			// There's no earlier version to fall back to on the first pass
+			Some(error_message) if first_pass => {
+				let message = CreateInteractionResponseMessage::new()
+					.ephemeral(true)
+					.content(error_message);
+				modal_interaction
+					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+					.await
+					.into_diagnostic()?;
+				return Ok(None);
+			}
+			Some(_) => (),
+			None => *pending_embed = edited_embed,
+		}
+		first_pass = false;
+
+		let preview_choice =
+			show_preview(ctx, &modal_interaction, guild, pending_embed, error_message.as_deref()).await?;
+		let (next_page, interaction) = match preview_choice {
+			PreviewChoice::Publish(interaction) => return Ok(Some(interaction)),
+			PreviewChoice::EditContent(interaction) => (ModalPage::Content, interaction),
+			PreviewChoice::EditDetails(interaction) => (ModalPage::Details, interaction),
+			PreviewChoice::EditAuthorFooter(interaction) => (ModalPage::AuthorFooter, interaction),
+			PreviewChoice::Discard(interaction) => {
+				let message = CreateInteractionResponseMessage::new()
+					.ephemeral(true)
+					.content(discard_message);
+				interaction
+					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+					.await
+					.into_diagnostic()?;
+				return Ok(None);
+			}
+			PreviewChoice::TimedOut => return Ok(None),
+		};
+
+		let modal = match next_page {
+			ModalPage::Content => content_modal(modal_title, pending_embed),
+			ModalPage::Details => details_modal(modal_title, pending_embed),
+			ModalPage::AuthorFooter => author_footer_modal(modal_title, pending_embed),
+		};
+		let Some(response) = interaction.quick_modal(ctx, modal).await.into_diagnostic()? else {
+			return Ok(None);
+		};
+		modal_response = response;
+		modal_page = next_page;
+	}
+}
+
+/// Sends the rendering of the complete published message set, as it will be with the pending embed in place, as
+/// followups to the interaction. Returns the IDs of the sent followups.
+async fn send_preview_messages(
+	ctx: &Context,
+	interaction: &ModalInteraction,
+	guild: GuildId,
+	pending_embed: &EmbedData,
+) -> miette::Result<Vec<MessageId>> {
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let channel_messages = {
+		let mut db_connection = db_connection.lock().await;
+		let mut embeds: Vec<EmbedData> = embed_data::table
+			.filter(embed_data::guild.eq(sql_guild_id))
+			.order(embed_data::embed_part_sequence_number.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		match embeds.iter_mut().find(|embed| embed.id == pending_embed.id) {
+			Some(embed) => *embed = pending_embed.clone(),
+			None => embeds.push(pending_embed.clone()),
+		}
+		build_channel_messages(&mut db_connection, guild, embeds)?
+	};
+
+	let mut followup_ids: Vec<MessageId> = Vec::new();
+	for (channel_id, embed_groups) in channel_messages {
+		let message_count = embed_groups.len();
+		for (message_index, embed_group) in embed_groups.into_iter().enumerate() {
+			let followup = CreateInteractionResponseFollowup::new()
+				.ephemeral(true)
+				.content(format!(
+					"<#{}> (message {} of {})",
+					channel_id.get(),
+					message_index + 1,
+					message_count
+				))
+				.embeds(embed_group);
+			let followup = interaction
+				.create_followup(&ctx.http, followup)
+				.await
+				.into_diagnostic()?;
+			followup_ids.push(followup.id);
+		}
+	}
+
+	Ok(followup_ids)
+}
+
+/// Removes previously sent preview followups
+async fn delete_preview_messages(ctx: &Context, interaction: &ModalInteraction, followup_ids: Vec<MessageId>) {
+	for followup_id in followup_ids {
+		// The preview may already have been dismissed by the user
+		let _ = interaction.delete_followup(&ctx.http, followup_id).await;
+	}
+}
+
+/// Responds to the modal interaction with an ephemeral preview of the pending embed and waits for the user to choose
+/// what to do with the change. Toggling the timestamp is handled here, since it doesn't need a modal. If the last edit
+/// was rejected, its error is shown above the preview.
+async fn show_preview(
+	ctx: &Context,
+	interaction: &ModalInteraction,
+	guild: GuildId,
+	pending_embed: &mut EmbedData,
+	edit_error: Option<&str>,
+) -> miette::Result<PreviewChoice> {
+	let button_ids = PreviewButtonIds::new();
+	let mut preview_content = String::from("# Preview\nThis is how the partner list will look with your changes. Nothing will be published until you click \"Publish\".");
+	if let Some(edit_error) = edit_error {
+		preview_content.push_str(&format!("\n**Your last edit wasn't applied:** {}", edit_error));
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(&preview_content)
+		.components(button_ids.button_rows(pending_embed.show_timestamp));
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let mut followup_ids = send_preview_messages(ctx, interaction, guild, pending_embed).await?;
+
+	let choice = loop {
+		let Some(button_interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(button_ids.all())
+			.timeout(Duration::from_secs(600))
+			.await
+		else {
+			break PreviewChoice::TimedOut;
+		};
+		if !matches!(button_interaction.data.kind, ComponentInteractionDataKind::Button) {
+			bail!(
+				"Unexpected interaction type received for embed preview: {:?}",
+				button_interaction.data.kind
+			);
+		}
+		let custom_id = button_interaction.data.custom_id.clone();
+		if custom_id == button_ids.publish {
+			break PreviewChoice::Publish(button_interaction);
+		}
+		if custom_id == button_ids.edit_content {
+			break PreviewChoice::EditContent(button_interaction);
+		}
+		if custom_id == button_ids.edit_details {
+			break PreviewChoice::EditDetails(button_interaction);
+		}
+		if custom_id == button_ids.edit_author_footer {
+			break PreviewChoice::EditAuthorFooter(button_interaction);
+		}
+		if custom_id == button_ids.discard {
+			break PreviewChoice::Discard(button_interaction);
+		}
+		if custom_id == button_ids.timestamp {
+			pending_embed.show_timestamp = !pending_embed.show_timestamp;
+			let message = CreateInteractionResponseMessage::new()
+				.content(&preview_content)
+				.components(button_ids.button_rows(pending_embed.show_timestamp));
+			button_interaction
+				.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+				.await
+				.into_diagnostic()?;
+
+			delete_preview_messages(ctx, interaction, followup_ids).await;
+			followup_ids = send_preview_messages(ctx, interaction, guild, pending_embed).await?;
+		}
+	};
+
+	delete_preview_messages(ctx, interaction, followup_ids).await;
+	let preview_result = match &choice {
+		PreviewChoice::Publish(_) => "Changes saved.",
+		PreviewChoice::EditContent(_) | PreviewChoice::EditDetails(_) | PreviewChoice::EditAuthorFooter(_) => {
+			"Editing again..."
+		}
+		PreviewChoice::Discard(_) => "Changes discarded.",
+		PreviewChoice::TimedOut => "Preview timed out; changes were discarded.",
+	};
+	let message = EditInteractionResponse::new()
+		.content(preview_result)
+		.components(Vec::new());
+	interaction.edit_response(&ctx.http, message).await.into_diagnostic()?;
+
+	Ok(choice)
+}
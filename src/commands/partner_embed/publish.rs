@@ -0,0 +1,47 @@
+use crate::database::get_database_connection;
+use crate::sync::embed_queue::queue_embed_publish;
+use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse};
+use serenity::client::Context;
+use serenity::model::application::CommandInteraction;
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partner embed command was run outside of a guild");
+	};
+
+	{
+		let db_connection = get_database_connection(ctx).await;
+		let mut db_connection = db_connection.lock().await;
+		if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+			return Ok(());
+		}
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Publishing the partner list...");
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	// The publish goes through the update worker so it can't overlap with other updates to the same messages
+	let published = queue_embed_publish(ctx, guild).await.await.unwrap_or(false);
+	let message = if published {
+		EditInteractionResponse::new().content("Published the partner list.")
+	} else {
+		EditInteractionResponse::new()
+			.content("Publishing the partner list failed. Check that the bot can post in the embed channels.")
+	};
+	command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+
+	if published {
+		let log_entry = log_embed(LogKind::Changed, "Partner list published", Some(command.user.id));
+		send_staff_log(ctx, guild, log_entry).await?;
+	}
+
+	Ok(())
+}
@@ -1,8 +1,9 @@
 use crate::database::get_database_connection;
 use crate::models::EmbedData;
 use crate::schema::embed_data;
-use crate::sync::embed::update_embed;
+use crate::sync::embed_queue::queue_embed_update;
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
@@ -178,7 +179,14 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 			.into_diagnostic()?;
 	}
 
-	update_embed(ctx, guild).await?;
+	let log_entry = log_embed(LogKind::Removed, "Embed removed", Some(command.user.id)).field(
+		"Embed",
+		&removing_embed.embed_name,
+		true,
+	);
+	send_staff_log(ctx, guild, log_entry).await?;
+
+	queue_embed_update(ctx, guild).await;
 
 	Ok(())
 }
@@ -1,8 +1,9 @@
 use crate::database::get_database_connection;
 use crate::models::EmbedData;
 use crate::schema::embed_data;
-use crate::sync::embed::update_embed;
+use crate::sync::embed_queue::queue_embed_update;
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
@@ -203,7 +204,19 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		command.edit_response(&ctx.http, message).await.into_diagnostic()?;
 	}
 
-	update_embed(ctx, guild).await?;
+	let new_order: Vec<String> = reordered_embeds
+		.iter()
+		.enumerate()
+		.map(|(embed_index, embed)| format!("{}. {}", embed_index + 1, embed.embed_name))
+		.collect();
+	let log_entry = log_embed(LogKind::Changed, "Embeds reordered", Some(command.user.id)).field(
+		"New order",
+		new_order.join("\n"),
+		false,
+	);
+	send_staff_log(ctx, guild, log_entry).await?;
+
+	queue_embed_update(ctx, guild).await;
 
 	Ok(())
 }
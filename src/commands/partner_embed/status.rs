@@ -0,0 +1,182 @@
+use crate::database::get_database_connection;
+use crate::models::PublishedEmbedSummary;
+use crate::schema::{guild_settings, published_embed_summaries, published_messages};
+use crate::sync::embed::build_embed_summaries;
+use crate::utils::setup_check::guild_setup_check_with_reply;
+use diesel::prelude::*;
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::CommandInteraction;
+
+/// Leaves room in the message for the closing lines after the diff is cut off
+const MAX_STATUS_LENGTH: usize = 1900;
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partner embed command was run outside of a guild");
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (draft_mode, published_summaries, current_summaries, has_published_messages) = {
+		let mut db_connection = db_connection.lock().await;
+		if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+			return Ok(());
+		}
+
+		let draft_mode: bool = guild_settings::table
+			.find(sql_guild_id)
+			.select(guild_settings::draft_mode)
+			.first(&mut *db_connection)
+			.into_diagnostic()?;
+		let published_summaries: Vec<PublishedEmbedSummary> = published_embed_summaries::table
+			.filter(published_embed_summaries::guild_id.eq(sql_guild_id))
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let current_summaries = build_embed_summaries(&mut db_connection, guild)?;
+		let published_message_count: i64 = published_messages::table
+			.filter(published_messages::guild_id.eq(sql_guild_id))
+			.count()
+			.get_result(&mut *db_connection)
+			.into_diagnostic()?;
+
+		(
+			draft_mode,
+			published_summaries,
+			current_summaries,
+			published_message_count > 0,
+		)
+	};
+
+	let mut changes: Vec<String> = Vec::new();
+	for current in current_summaries.iter() {
+		let embed_name = summary_embed_name(&current.summary);
+		match published_summaries
+			.iter()
+			.find(|published| published.embed_id == current.embed_id)
+		{
+			Some(published) => {
+				if published.summary != current.summary {
+					changes.push(format!(
+						"Changed embed ({})\n```diff\n{}\n```",
+						embed_name,
+						line_diff(&published.summary, &current.summary)
+					));
+				}
+			}
+			None => changes.push(format!(
+				"New embed ({})\n```diff\n{}\n```",
+				embed_name,
+				added(&current.summary)
+			)),
+		}
+	}
+	for published in published_summaries.iter() {
+		if !current_summaries
+			.iter()
+			.any(|current| current.embed_id == published.embed_id)
+		{
+			let embed_name = summary_embed_name(&published.summary);
+			changes.push(format!("Removed embed ({})", embed_name));
+		}
+	}
+
+	let mut reply = if draft_mode {
+		String::from("Draft mode is on.")
+	} else {
+		String::from("Draft mode is off.")
+	};
+	// Published embeds are only recorded from the first publish after changes started being tracked, so until then,
+	// every embed is listed as new
+	if published_summaries.is_empty() && !current_summaries.is_empty() {
+		if has_published_messages {
+			reply.push_str(
+				" The partner list was last published before changes were tracked, so every embed is listed as new until it's published again.",
+			);
+		} else {
+			reply.push_str(" The partner list hasn't been published yet.");
+		}
+	}
+	if changes.is_empty() {
+		reply.push_str(" There are no pending changes.");
+	} else {
+		reply.push_str(" Pending changes:");
+		let change_count = changes.len();
+		for (index, change) in changes.into_iter().enumerate() {
+			let remaining_length = MAX_STATUS_LENGTH.saturating_sub(reply.len() + 1);
+			if change.len() > remaining_length {
+				// A change that doesn't fit is cut down to the space left, so a single large change still shows
+				let truncated_change = truncate_change(&change, remaining_length);
+				if !truncated_change.is_empty() {
+					reply.push('\n');
+					reply.push_str(&truncated_change);
+				}
+				if index + 1 < change_count {
+					reply.push_str("\n...and more changes");
+				}
+				break;
+			}
+			reply.push('\n');
+			reply.push_str(&change);
+		}
+		reply.push_str("\nUse `/partner_embed publish` to publish these changes.");
+	}
+
+	let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reply);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+/// Gets the embed name from the first line of a summary
+fn summary_embed_name(summary: &str) -> &str {
+	let name_line = summary.lines().next().unwrap_or_default();
+	name_line.strip_prefix("Name: ").unwrap_or(name_line)
+}
+
+/// Lists the lines removed from and added to a summary
+fn line_diff(old: &str, new: &str) -> String {
+	let old_lines: Vec<&str> = old.lines().collect();
+	let new_lines: Vec<&str> = new.lines().collect();
+	let removed_lines = old_lines
+		.iter()
+		.filter(|line| !new_lines.contains(line))
+		.map(|line| format!("- {}", line));
+	let added_lines = new_lines
+		.iter()
+		.filter(|line| !old_lines.contains(line))
+		.map(|line| format!("+ {}", line));
+	let diff_lines: Vec<String> = removed_lines.chain(added_lines).collect();
+	diff_lines.join("\n")
+}
+
+/// Lists all the lines of a summary as added
+fn added(summary: &str) -> String {
+	let lines: Vec<String> = summary.lines().map(|line| format!("+ {}", line)).collect();
+	lines.join("\n")
+}
+
+/// Cuts a change down to at most the given length in bytes, closing its diff block if it has one. Gives an empty
+/// string if there isn't room for any of the change.
+fn truncate_change(change: &str, max_length: usize) -> String {
+	let (body, ending) = match change.strip_suffix("\n```") {
+		Some(body) => (body, "\n...\n```"),
+		None => (change, "..."),
+	};
+	let Some(mut cut_length) = max_length.checked_sub(ending.len()) else {
+		return String::new();
+	};
+	while !body.is_char_boundary(cut_length) {
+		cut_length -= 1;
+	}
+	let kept_body = &body[..cut_length];
+	// Cutting into the opening of the diff block would leave it unbalanced
+	if ending.ends_with("```") && !kept_body.contains("```diff\n") {
+		return String::new();
+	}
+	format!("{}{}", kept_body, ending)
+}
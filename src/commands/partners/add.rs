@@ -1,8 +1,12 @@
 use crate::database::get_database_connection;
 use crate::models::{Partner, PartnerCategory};
 use crate::schema::{partner_categories, partners};
-use crate::sync::embed::update_embed;
+use crate::sync::announcement::{build_partner_announcement, AnnouncementKind};
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::invite::resolve_partner_invite;
+use crate::utils::partner_status::PartnerStatus;
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use diesel::result::{DatabaseErrorKind, Error as DbError};
 use miette::{bail, ensure, IntoDiagnostic, Severity};
@@ -15,8 +19,6 @@ use serenity::collector::ComponentInteractionCollector;
 use serenity::model::application::{
 	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, ResolvedOption, ResolvedValue,
 };
-use serenity::model::invite::Invite;
-use serenity::utils::parse_invite;
 use std::time::Duration;
 
 pub async fn execute(
@@ -78,36 +80,10 @@ pub async fn execute(
 		"Not all required options passed to partners add command"
 	);
 
-	let invite_code = parse_invite(invite_link);
-
-	// Sometimes, when parsing the invite code, it can maintain an initial slash before the actual code.
-	// Somehow, this doesn't seem to break anything in Serenity, and Discord seems to accept it just fine (or Serenity
-	// removes the slash), but we want not to have it.
-	let invite_code = match invite_code.strip_prefix('/') {
-		Some(code) => code,
-		None => invite_code,
-	};
-
-	let invite = match Invite::get(ctx, invite_code, false, true, None).await {
-		Ok(invite) => invite,
-		Err(_) => {
-			let message = CreateInteractionResponseMessage::new()
-				.ephemeral(true)
-				.content("The invite link is invalid.");
-			command
-				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
-				.await
-				.into_diagnostic()?;
-			return Ok(());
-		}
-	};
-
-	let partner_guild = match invite.guild {
-		Some(invite_guild) => invite_guild,
-		None => {
-			let message = CreateInteractionResponseMessage::new()
-				.ephemeral(true)
-				.content("The invite link is invalid; could not retrieve a server for it.");
+	let (invite_code, partner_guild, member_count) = match resolve_partner_invite(ctx, invite_link).await {
+		Ok(invite) => (invite.code, invite.guild, invite.member_count),
+		Err(reason) => {
+			let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reason);
 			command
 				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
 				.await
@@ -116,17 +92,6 @@ pub async fn execute(
 		}
 	};
 
-	if invite.expires_at.is_some() {
-		let message = CreateInteractionResponseMessage::new()
-			.ephemeral(true)
-			.content("The invite link is not permanent.");
-		command
-			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
-			.await
-			.into_diagnostic()?;
-		return Ok(());
-	}
-
 	let category_select_id = cuid2::create_id();
 	let submit_button_id = cuid2::create_id();
 	let cancel_button_id = cuid2::create_id();
@@ -224,12 +189,13 @@ pub async fn execute(
 		return Ok(());
 	}
 
+	let partner_guild_name = partner_guild.name;
 	if display_name.is_empty() {
-		display_name = partner_guild.name;
+		display_name = partner_guild_name.clone();
 	}
 	let partner_guild = partner_guild.id;
 
-	let insert_result = {
+	let (insert_result, category_name, announcement) = {
 		let mut db_connection = db_connection.lock().await;
 		let selected_category: Option<PartnerCategory> = partner_categories::table
 			.filter(
@@ -240,7 +206,7 @@ pub async fn execute(
 			.first(&mut *db_connection)
 			.optional()
 			.into_diagnostic()?;
-		if selected_category.is_none() {
+		let Some(selected_category) = selected_category else {
 			let message = CreateInteractionResponseMessage::new()
 				.ephemeral(true)
 				.content("The category you selected is no longer valid.");
@@ -249,22 +215,34 @@ pub async fn execute(
 				.await
 				.into_diagnostic()?;
 			return Ok(());
-		}
+		};
 
+		let partnership_id = cuid2::create_id();
 		let new_partner = Partner {
-			partnership_id: cuid2::create_id(),
+			partnership_id: partnership_id.clone(),
 			guild: sql_guild_id,
 			category: partner_category,
 			partner_guild: partner_guild.get() as i64,
 			display_name: display_name.clone(),
-			invite_code: invite_code.to_string(),
+			invite_code: invite_code.clone(),
+			description: String::new(),
+			emoji: String::new(),
+			status: PartnerStatus::Active.as_str().to_string(),
+			partner_guild_name,
+			member_count,
 		};
 		let insert_result: QueryResult<_> = diesel::insert_into(partners::table)
 			.values(new_partner)
 			.execute(&mut *db_connection);
-		insert_result
+		let announcement = if insert_result.is_ok() {
+			build_partner_announcement(&mut db_connection, guild, AnnouncementKind::Added, &partnership_id)?
+		} else {
+			None
+		};
+		(insert_result, selected_category.name, announcement)
 	};
 
+	let partner_added = insert_result.is_ok();
 	let message = match insert_result {
 		Ok(_) => CreateInteractionResponseMessage::new().content(format!(
 			"Added [{}](https://discord.gg/{}) as a partner!",
@@ -285,7 +263,21 @@ pub async fn execute(
 		.await
 		.into_diagnostic()?;
 
-	update_embed(ctx, guild).await?;
+	if partner_added {
+		let embed = log_embed(LogKind::Added, "Partner added", Some(command.user.id))
+			.field(
+				"Partner",
+				format!("[{}](https://discord.gg/{})", display_name, invite_code),
+				true,
+			)
+			.field("Category", category_name, true);
+		send_staff_log(ctx, guild, embed).await?;
+	}
+	if let Some(announcement) = announcement {
+		announcement.send(ctx).await?;
+	}
+
+	queue_embed_update(ctx, guild).await;
 
 	Ok(())
 }
@@ -1,8 +1,11 @@
 use crate::database::get_database_connection;
 use crate::models::{Partner, PartnerUser};
 use crate::schema::{guild_settings, partner_users, partners};
+use crate::sync::role::RoleExemptions;
 use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::partner_status::PartnerStatus;
 use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use diesel::result::{DatabaseErrorKind, Error as DbError};
 use miette::{bail, IntoDiagnostic};
@@ -265,13 +268,15 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 	}
 
 	let mut complain_about_role_permissions = false;
-	if let Some(partner_role_id) = partner_role_id {
+	let partner_status: PartnerStatus = partner_data.status.parse()?;
+	if let (Some(partner_role_id), true) = (partner_role_id, partner_status.grants_role()) {
 		let guild_data = Guild::get(&ctx.http, guild).await.into_diagnostic()?;
 		let member = guild_data
 			.member(&ctx.http, representative_user)
 			.await
 			.into_diagnostic()?;
-		if !member.roles.iter().any(|role| role.get() == partner_role_id) {
+		let exemptions = RoleExemptions::load(guild, &mut db_connection)?;
+		if !exemptions.is_exempt(&member) && !member.roles.iter().any(|role| role.get() == partner_role_id) {
 			let add_role_result = member.add_role(&ctx.http, partner_role_id).await;
 			if let Err(SerenityError::Http(HttpError::UnsuccessfulRequest(ErrorResponse {
 				status_code: StatusCode::FORBIDDEN,
@@ -285,6 +290,8 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		}
 	}
 
+	drop(db_connection);
+
 	let mut message_content = format!(
 		"Added <@{}> as a partner for {}.",
 		representative_user.get(),
@@ -301,5 +308,10 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		.await
 		.into_diagnostic()?;
 
+	let embed = log_embed(LogKind::Added, "Partner representative added", Some(command.user.id))
+		.field("Partner", &partner_data.display_name, true)
+		.field("Representative", format!("<@{}>", representative_user.get()), true);
+	send_staff_log(ctx, guild, embed).await?;
+
 	Ok(())
 }
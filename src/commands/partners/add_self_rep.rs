@@ -3,6 +3,7 @@ use crate::models::{Partner, PartnerSelfUser};
 use crate::schema::{partner_self_users, partners};
 use crate::utils::pagination::{get_partners_for_page, max_partner_page};
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use diesel::result::{DatabaseErrorKind, Error as DbError};
 use miette::{bail, IntoDiagnostic};
@@ -207,6 +208,7 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 	let insert_result = diesel::insert_into(partner_self_users::table)
 		.values(new_value)
 		.execute(&mut *db_connection);
+	drop(db_connection);
 
 	match insert_result {
 		Ok(_) => {
@@ -221,6 +223,11 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
 				.await
 				.into_diagnostic()?;
+
+			let embed = log_embed(LogKind::Added, "Our representative added", Some(command.user.id))
+				.field("Partner", &partner.display_name, true)
+				.field("Representative", format!("<@{}>", user.get()), true);
+			send_staff_log(ctx, guild, embed).await?;
 		}
 		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
 			let message = CreateInteractionResponseMessage::new()
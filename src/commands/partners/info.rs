@@ -0,0 +1,197 @@
+use crate::database::get_database_connection;
+use crate::models::Partner;
+use crate::schema::{partner_categories, partner_self_users, partner_users, partners};
+use crate::utils::mutual::MutualityReport;
+use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::partner_status::PartnerStatus;
+use crate::utils::setup_check::guild_setup_check_with_reply;
+use diesel::prelude::*;
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateInteractionResponse, CreateInteractionResponseMessage,
+	CreateSelectMenu, CreateSelectMenuKind, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{CommandInteraction, ComponentInteraction, ComponentInteractionDataKind};
+use std::time::Duration;
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command used outside of a guild");
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let partners: Vec<Partner> = {
+		let mut db_connection = db_connection.lock().await;
+		if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+			return Ok(());
+		}
+
+		partners::table
+			.filter(partners::guild.eq(sql_guild_id))
+			.order(partners::display_name.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	if partners.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You have no partners to show.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let mut current_partner_page = 0;
+	let partner_select_options = get_partners_for_page(&partners, current_partner_page, "");
+
+	let partner_select_id = cuid2::create_id();
+	let partner_select = CreateSelectMenu::new(
+		&partner_select_id,
+		CreateSelectMenuKind::String {
+			options: partner_select_options,
+		},
+	);
+	let partner_row = CreateActionRow::SelectMenu(partner_select);
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Choose the partner to show.")
+		.components(vec![partner_row]);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let (interaction, partner_id): (ComponentInteraction, String) = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![partner_select_id.clone()])
+			.timeout(Duration::from_secs(30))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("Selection timed out.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				let value = values.first().cloned().unwrap_or_default();
+				if interaction.data.custom_id == partner_select_id {
+					if value == "<" {
+						current_partner_page = current_partner_page.saturating_sub(1);
+					} else if value == ">" {
+						current_partner_page = (current_partner_page + 1).min(max_partner_page(&partners));
+					} else {
+						break (interaction, value);
+					}
+
+					let partner_select_options = get_partners_for_page(&partners, current_partner_page, "");
+					let partner_select = CreateSelectMenu::new(
+						&partner_select_id,
+						CreateSelectMenuKind::String {
+							options: partner_select_options,
+						},
+					);
+					let partner_row = CreateActionRow::SelectMenu(partner_select);
+
+					let message = EditInteractionResponse::new().components(vec![partner_row]);
+					command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+						.await
+						.into_diagnostic()?;
+				}
+			}
+			_ => bail!(
+				"Unexpected interaction occurred in partners info command: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	let (partner_data, category_name, reps, self_reps, mutuality) = {
+		let mut db_connection = db_connection.lock().await;
+		let partner_data: Option<Partner> = partners::table
+			.filter(
+				partners::partnership_id
+					.eq(&partner_id)
+					.and(partners::guild.eq(sql_guild_id)),
+			)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let Some(partner_data) = partner_data else {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("The selected partner is not valid.");
+			interaction
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		};
+
+		let category_name: String = partner_categories::table
+			.find(&partner_data.category)
+			.select(partner_categories::name)
+			.first(&mut *db_connection)
+			.into_diagnostic()?;
+		let reps: Vec<i64> = partner_users::table
+			.filter(partner_users::partnership_id.eq(&partner_id))
+			.select(partner_users::user_id)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let self_reps: Vec<i64> = partner_self_users::table
+			.filter(partner_self_users::partnership.eq(&partner_id))
+			.select(partner_self_users::user_id)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let mutuality_report = MutualityReport::load(&mut db_connection, sql_guild_id).into_diagnostic()?;
+		let mutuality = mutuality_report.partners.get(&partner_id).copied();
+
+		(partner_data, category_name, reps, self_reps, mutuality)
+	};
+
+	let status: PartnerStatus = partner_data.status.parse()?;
+	let user_list = |users: &[i64]| -> String {
+		if users.is_empty() {
+			String::from("none")
+		} else {
+			let mentions: Vec<String> = users.iter().map(|user| format!("<@{}>", user)).collect();
+			mentions.join(", ")
+		}
+	};
+
+	let mut message_lines = vec![
+		format!("**{}**", partner_data.display_name),
+		format!("Status: {}", status.display_name()),
+		format!("Category: {}", category_name),
+		format!("Invite: https://discord.gg/{}", partner_data.invite_code),
+		format!("Server ID: {}", partner_data.partner_guild),
+	];
+	if !partner_data.description.is_empty() {
+		message_lines.push(format!("Description: {}", partner_data.description));
+	}
+	message_lines.push(format!("Representatives: {}", user_list(&reps)));
+	message_lines.push(format!("Our representatives: {}", user_list(&self_reps)));
+	if let Some(mutuality) = mutuality {
+		message_lines.push(format!("Partnership: {}", mutuality.description()));
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.content(message_lines.join("\n"))
+		.allowed_mentions(CreateAllowedMentions::new());
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::utils::partner_status::PartnerStatus;
 use miette::{bail, ensure, Severity};
 use serenity::builder::{CreateCommand, CreateCommandOption};
 use serenity::client::Context;
@@ -7,15 +8,25 @@ use serenity::model::permissions::Permissions;
 mod add;
 mod add_rep;
 mod add_self_rep;
+mod info;
 mod list_reps;
 mod list_self_reps;
+mod mutual_report;
 mod remove;
 mod remove_rep;
 mod remove_self_rep;
+mod request;
+mod restore;
 mod set_category;
+mod set_details;
+mod set_invite;
 mod set_name;
+mod set_status;
+mod user_data;
 mod user_rep_list;
 
+pub use request::{handle_request_button, REQUEST_BUTTON_PREFIX};
+
 pub fn definition() -> CreateCommand {
 	let partner_add_invite_link = CreateCommandOption::new(
 		CommandOptionType::String,
@@ -43,19 +54,77 @@ pub fn definition() -> CreateCommand {
 		"list_reps",
 		"Lists representatives for a particular partner",
 	);
+	let info_command =
+		CreateCommandOption::new(CommandOptionType::SubCommand, "info", "Shows the details of a partner");
+	let mutual_report_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"mutual_report",
+		"Shows which partners using this bot list us as a partner too",
+	);
+	let remove_partner_reason = CreateCommandOption::new(
+		CommandOptionType::String,
+		"reason",
+		"Why the partnership is being removed; kept with the archived partner",
+	)
+	.max_length(500);
 	let remove_partner_command =
-		CreateCommandOption::new(CommandOptionType::SubCommand, "remove", "Removes a partner server");
+		CreateCommandOption::new(CommandOptionType::SubCommand, "remove", "Removes a partner server")
+			.add_sub_option(remove_partner_reason);
+	let restore_partner_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"restore",
+		"Restores a removed partner along with its representatives",
+	);
 	let remove_representative_command = CreateCommandOption::new(
 		CommandOptionType::SubCommand,
 		"remove_rep",
 		"Removes a representative for a particular partner",
 	);
+	let request_invite_link = CreateCommandOption::new(
+		CommandOptionType::String,
+		"invite_link",
+		"A permanent invite link for the server to partner with",
+	)
+	.required(true);
+	let request_our_invite_link = CreateCommandOption::new(
+		CommandOptionType::String,
+		"our_invite_link",
+		"A permanent invite link for this server for them to list",
+	)
+	.required(true);
+	let request_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"request",
+		"Asks another server using this bot to partner with this server",
+	)
+	.add_sub_option(request_invite_link)
+	.add_sub_option(request_our_invite_link);
+
 	let set_category_command = CreateCommandOption::new(
 		CommandOptionType::SubCommand,
 		"set_category",
 		"Sets the partner category for an existing partner",
 	);
 
+	let set_invite_link = CreateCommandOption::new(
+		CommandOptionType::String,
+		"invite_link",
+		"The partner's new invite link",
+	)
+	.required(true);
+	let set_invite_allow_server_change = CreateCommandOption::new(
+		CommandOptionType::Boolean,
+		"allow_server_change",
+		"Allow the invite to be for a different server than before (administrators only)",
+	);
+	let set_invite_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_invite",
+		"Changes the invite link for a partner",
+	)
+	.add_sub_option(set_invite_link)
+	.add_sub_option(set_invite_allow_server_change);
+
 	let new_name =
 		CreateCommandOption::new(CommandOptionType::String, "new_display_name", "The new name to use").required(true);
 	let set_name_command = CreateCommandOption::new(
@@ -65,6 +134,32 @@ pub fn definition() -> CreateCommand {
 	)
 	.add_sub_option(new_name);
 
+	let mut set_status_status =
+		CreateCommandOption::new(CommandOptionType::String, "status", "The new partnership status").required(true);
+	for status in PartnerStatus::ALL {
+		set_status_status = set_status_status.add_string_choice(status.display_name(), status.as_str());
+	}
+	set_status_status =
+		set_status_status.add_string_choice("Ended (archives the partner)", set_status::ENDED_STATUS_CHOICE);
+	let set_status_reason = CreateCommandOption::new(
+		CommandOptionType::String,
+		"reason",
+		"Why the partnership ended; only used when ending it",
+	)
+	.max_length(500);
+	let set_status_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_status",
+		"Changes whether a partnership is pending, active, on hiatus, or ended",
+	)
+	.add_sub_option(set_status_status)
+	.add_sub_option(set_status_reason);
+	let set_details_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_details",
+		"Sets the description and emoji shown for a partner",
+	);
+
 	let add_self_representative_command = CreateCommandOption::new(
 		CommandOptionType::SubCommand,
 		"add_self_rep",
@@ -94,6 +189,15 @@ pub fn definition() -> CreateCommand {
 	)
 	.add_sub_option(partner_rep_list_user);
 
+	let user_data_user =
+		CreateCommandOption::new(CommandOptionType::User, "user", "The user whose data to show").required(true);
+	let user_data = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"user_data",
+		"Shows all partner data stored for a user, with the option to erase it",
+	)
+	.add_sub_option(user_data_user);
+
 	CreateCommand::new("partners")
 		.kind(CommandType::ChatInput)
 		.default_member_permissions(Permissions::MANAGE_GUILD)
@@ -101,15 +205,23 @@ pub fn definition() -> CreateCommand {
 		.description("Manages partners and their representatives for the server")
 		.add_option(add_partner_command)
 		.add_option(add_representative_command)
+		.add_option(info_command)
 		.add_option(list_representatives_command)
+		.add_option(mutual_report_command)
 		.add_option(remove_partner_command)
 		.add_option(remove_representative_command)
+		.add_option(request_command)
+		.add_option(restore_partner_command)
 		.add_option(set_category_command)
+		.add_option(set_details_command)
+		.add_option(set_invite_command)
 		.add_option(set_name_command)
+		.add_option(set_status_command)
 		.add_option(add_self_representative_command)
 		.add_option(list_self_representative_command)
 		.add_option(remove_self_representative_command)
 		.add_option(user_rep_list)
+		.add_option(user_data)
 }
 
 pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
@@ -127,13 +239,21 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		"add" => add::execute(ctx, command, subcommand_options).await,
 		"add_rep" => add_rep::execute(ctx, command).await,
 		"add_self_rep" => add_self_rep::execute(ctx, command).await,
+		"info" => info::execute(ctx, command).await,
 		"list_reps" => list_reps::execute(ctx, command).await,
 		"list_self_reps" => list_self_reps::execute(ctx, command).await,
-		"remove" => remove::execute(ctx, command).await,
+		"mutual_report" => mutual_report::execute(ctx, command).await,
+		"remove" => remove::execute(ctx, command, subcommand_options).await,
 		"remove_rep" => remove_rep::execute(ctx, command).await,
+		"request" => request::execute(ctx, command, subcommand_options).await,
+		"restore" => restore::execute(ctx, command).await,
 		"remove_self_rep" => remove_self_rep::execute(ctx, command).await,
 		"set_category" => set_category::execute(ctx, command).await,
+		"set_details" => set_details::execute(ctx, command).await,
+		"set_invite" => set_invite::execute(ctx, command, subcommand_options).await,
 		"set_name" => set_name::execute(ctx, command, subcommand_options).await,
+		"set_status" => set_status::execute(ctx, command, subcommand_options).await,
+		"user_data" => user_data::execute(ctx, command, subcommand_options).await,
 		"user_rep_list" => user_rep_list::execute(ctx, command, subcommand_options).await,
 		_ => bail!("Unexpected subcommand for partners command: {:?}", subcommand),
 	}
@@ -0,0 +1,105 @@
+use crate::database::get_database_connection;
+use crate::models::Partner;
+use crate::schema::partners;
+use crate::utils::mutual::{Mutuality, MutualityReport};
+use crate::utils::setup_check::guild_setup_check_with_reply;
+use diesel::prelude::*;
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{CreateAllowedMentions, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::CommandInteraction;
+use serenity::model::id::GuildId;
+
+/// Discord's limit on message length
+const MAX_MESSAGE_LEN: usize = 2000;
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command used outside of a guild");
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (partners, report) = {
+		let mut db_connection = db_connection.lock().await;
+		if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+			return Ok(());
+		}
+
+		let partners: Vec<Partner> = partners::table
+			.filter(partners::guild.eq(sql_guild_id))
+			.order(partners::display_name.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let report = MutualityReport::load(&mut db_connection, sql_guild_id).into_diagnostic()?;
+		(partners, report)
+	};
+
+	let partners_with = |mutuality: Mutuality| -> Vec<String> {
+		partners
+			.iter()
+			.filter(|partner| report.partners.get(&partner.partnership_id) == Some(&mutuality))
+			.map(|partner| format!("- {}", partner.display_name))
+			.collect()
+	};
+	let one_sided = partners_with(Mutuality::OneSided);
+	let mutual = partners_with(Mutuality::Mutual);
+	let unknown_count = partners_with(Mutuality::Unknown).len();
+	let unreciprocated: Vec<String> = report
+		.unreciprocated_guilds
+		.iter()
+		.map(|guild_id| {
+			let unreciprocated_guild = GuildId::new(*guild_id as u64);
+			match ctx.cache.guild(unreciprocated_guild) {
+				Some(guild) => format!("- {}", guild.name),
+				None => format!("- Server {}", guild_id),
+			}
+		})
+		.collect();
+
+	let mut message_lines: Vec<String> = Vec::new();
+	if !one_sided.is_empty() {
+		message_lines.push(String::from("**Partners that use this bot but don't list us:**"));
+		message_lines.extend(one_sided);
+	}
+	if !unreciprocated.is_empty() {
+		message_lines.push(String::from("**Servers that list us but aren't in our partner list:**"));
+		message_lines.extend(unreciprocated);
+	}
+	if !mutual.is_empty() {
+		message_lines.push(String::from("**Mutual partners:**"));
+		message_lines.extend(mutual);
+	}
+	if unknown_count > 0 {
+		message_lines.push(format!(
+			"{} partners don't use this bot, so whether they list us can't be checked.",
+			unknown_count
+		));
+	}
+	if message_lines.is_empty() {
+		message_lines.push(String::from("You have no current partners to check."));
+	}
+
+	let mut content = String::new();
+	for (index, line) in message_lines.iter().enumerate() {
+		let remaining_lines = message_lines.len() - index;
+		let overflow_note = format!("...and {} more lines", remaining_lines);
+		if content.len() + line.len() + overflow_note.len() + 2 > MAX_MESSAGE_LEN {
+			content.push_str(&overflow_note);
+			break;
+		}
+		content.push_str(line);
+		content.push('\n');
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(content)
+		.allowed_mentions(CreateAllowedMentions::new());
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
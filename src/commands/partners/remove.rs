@@ -1,29 +1,47 @@
 use crate::database::get_database_connection;
 use crate::models::{GuildSettings, Partner};
 use crate::schema::{guild_settings, partners};
-use crate::sync::embed::update_embed;
+use crate::sync::announcement::{build_partner_announcement, AnnouncementKind};
+use crate::sync::embed_queue::queue_embed_update;
 use crate::sync::role::sync_role_for_guild;
 use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::partner_archive::archive_partner;
 use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
-	CreateActionRow, CreateAllowedMentions, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
-	CreateSelectMenu, CreateSelectMenuKind, EditInteractionResponse,
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateEmbed, CreateInteractionResponse,
+	CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind, EditInteractionResponse,
 };
 use serenity::client::Context;
 use serenity::collector::ComponentInteractionCollector;
 use serenity::model::application::{
-	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind,
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, ResolvedOption, ResolvedValue,
 };
 use serenity::model::id::RoleId;
 use std::time::Duration;
 
-pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
 	let Some(guild) = command.guild_id else {
 		bail!("Partners command used outside of a guild");
 	};
 
+	let mut reason = String::new();
+	for option in options.iter() {
+		let ResolvedValue::String(value) = option.value else {
+			bail!("Non-string value passed to a string option");
+		};
+		match option.name {
+			"reason" => reason = value.trim().to_string(),
+			_ => bail!("Invalid option passed to partners remove command: {}", option.name),
+		}
+	}
+
 	let sql_guild_id = guild.get() as i64;
 	let db_connection = get_database_connection(ctx).await;
 	let (partner_role, partners) = {
@@ -195,28 +213,69 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		bail!("Partner selection desynchronized with partner list");
 	};
 
-	{
-		let mut db_connection = db_connection.lock().await;
-		diesel::delete(partners::table)
-			.filter(partners::partnership_id.eq(&partner_id))
-			.execute(&mut *db_connection)
-			.into_diagnostic()?;
-	}
+	let reply = format!(
+		"Removed {} as a partner. It can be brought back with `/partners restore`.",
+		partner_display_name
+	);
+	let log_entry = log_embed(LogKind::Removed, "Partner removed", Some(interaction.user.id)).field(
+		"Partner",
+		partner_display_name,
+		true,
+	);
+	archive_and_follow_up(ctx, &interaction, partner_role, &partner_id, &reason, reply, log_entry).await
+}
 
-	// TODO update embed
-	if let Some(partner_role) = partner_role {
-		sync_role_for_guild(ctx, guild, partner_role).await?;
-	}
+/// Archives a partner, then replies to the interaction and follows up on the partner no longer being current: the
+/// partner role, the staff log entry (with the reason added), the removal announcement, and the embeds
+pub async fn archive_and_follow_up(
+	ctx: &Context,
+	interaction: &ComponentInteraction,
+	partner_role: Option<RoleId>,
+	partner_id: &str,
+	reason: &str,
+	reply: String,
+	mut log_entry: CreateEmbed,
+) -> miette::Result<()> {
+	let Some(guild) = interaction.guild_id else {
+		bail!("Partner was archived outside of a guild");
+	};
+
+	let announcement = {
+		let db_connection = get_database_connection(ctx).await;
+		let mut db_connection = db_connection.lock().await;
+		let announcement =
+			build_partner_announcement(&mut db_connection, guild, AnnouncementKind::Removed, partner_id)?;
+		archive_partner(
+			&mut db_connection,
+			partner_id,
+			reason,
+			Some(interaction.user.id.get() as i64),
+		)
+		.into_diagnostic()?;
+		announcement
+	};
 
 	let message = CreateInteractionResponseMessage::new()
-		.content(format!("Removed {} as a partner.", partner_display_name))
+		.content(reply)
 		.allowed_mentions(CreateAllowedMentions::new());
 	interaction
 		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
 		.await
 		.into_diagnostic()?;
 
-	update_embed(ctx, guild).await?;
+	if let Some(partner_role) = partner_role {
+		sync_role_for_guild(ctx, guild, partner_role).await?;
+	}
+
+	if !reason.is_empty() {
+		log_entry = log_entry.field("Reason", reason, false);
+	}
+	send_staff_log(ctx, guild, log_entry).await?;
+	if let Some(announcement) = announcement {
+		announcement.send(ctx).await?;
+	}
+
+	queue_embed_update(ctx, guild).await;
 
 	Ok(())
 }
@@ -1,8 +1,11 @@
 use crate::database::get_database_connection;
 use crate::models::Partner;
 use crate::schema::{guild_settings, partner_users, partners};
+use crate::sync::role::RoleExemptions;
 use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::partner_status::PartnerStatus;
 use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::dsl::count_star;
 use diesel::prelude::*;
 use miette::{bail, IntoDiagnostic};
@@ -343,12 +346,20 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 
 	let mut complain_about_role_permissions = false;
 	if let Some(partner_role_id) = partner_role_id {
+		let role_statuses: Vec<&str> = PartnerStatus::ROLE_GRANTING
+			.iter()
+			.map(|status| status.as_str())
+			.collect();
 		let remaining_representing: i64 = partner_users::table
 			.filter(
 				partner_users::partnership_id
 					.eq_any(
 						partners::table
-							.filter(partners::guild.eq(sql_guild_id))
+							.filter(
+								partners::guild
+									.eq(sql_guild_id)
+									.and(partners::status.eq_any(role_statuses)),
+							)
 							.select(partners::partnership_id),
 					)
 					.and(partner_users::user_id.eq(sql_user_id)),
@@ -357,11 +368,12 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 			.first(&mut *db_connection)
 			.into_diagnostic()?;
 		if remaining_representing == 0 {
+			let exemptions = RoleExemptions::load(guild, &mut db_connection)?;
 			let guild_data = Guild::get(&ctx.http, guild).await.into_diagnostic()?;
 			let member = guild_data.member(&ctx.http, user_id).await;
 			match member {
 				Ok(member) => {
-					if member.roles.iter().any(|role| role.get() == partner_role_id) {
+					if !exemptions.is_exempt(&member) && member.roles.iter().any(|role| role.get() == partner_role_id) {
 						let remove_role_result = member.remove_role(&ctx.http, partner_role_id).await;
 						match remove_role_result {
 							Ok(_) => (),
@@ -382,6 +394,8 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		}
 	}
 
+	drop(db_connection);
+
 	let partner_display_name = partners
 		.iter()
 		.find(|partner| partner.partnership_id == partnership_id)
@@ -405,5 +419,14 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		.await
 		.into_diagnostic()?;
 
+	let embed = log_embed(
+		LogKind::Removed,
+		"Partner representative removed",
+		Some(command.user.id),
+	)
+	.field("Partner", partner_display_name, true)
+	.field("Representative", format!("<@{}>", user_id), true);
+	send_staff_log(ctx, guild, embed).await?;
+
 	Ok(())
 }
@@ -3,6 +3,7 @@ use crate::models::Partner;
 use crate::schema::{partner_self_users, partners};
 use crate::utils::pagination::{get_partners_for_page, max_partner_page};
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
@@ -331,6 +332,8 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		.execute(&mut *db_connection)
 		.into_diagnostic()?;
 
+	drop(db_connection);
+
 	let message_content = format!(
 		"Removed <@{}> as a representative for {}.",
 		user_id, partner_display_name
@@ -343,5 +346,10 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		.await
 		.into_diagnostic()?;
 
+	let embed = log_embed(LogKind::Removed, "Our representative removed", Some(command.user.id))
+		.field("Partner", partner_display_name, true)
+		.field("Representative", format!("<@{}>", user_id), true);
+	send_staff_log(ctx, guild, embed).await?;
+
 	Ok(())
 }
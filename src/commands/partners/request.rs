@@ -0,0 +1,780 @@
+use crate::database::get_database_connection;
+use crate::models::{GuildSettings, Partner, PartnerCategory, PartnerSelfUser, PartnerUser, PartnershipRequest};
+use crate::schema::{
+	guild_settings, partner_categories, partner_self_users, partner_users, partners, partnership_requests,
+};
+use crate::sync::announcement::{build_partner_announcement, AnnouncementKind};
+use crate::sync::embed_queue::queue_embed_update;
+use crate::sync::role::sync_role_for_guild;
+use crate::utils::invite::resolve_partner_invite;
+use crate::utils::partner_status::PartnerStatus;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{deliver_staff_log_with_components, log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DbError};
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateEmbed, CreateInteractionResponse,
+	CreateInteractionResponseMessage, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+	EditInteractionResponse, EditMessage,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, ResolvedOption, ResolvedValue,
+};
+use serenity::model::id::{GuildId, RoleId, UserId};
+use serenity::model::permissions::Permissions;
+use std::time::Duration;
+
+/// The custom ID prefix for the buttons on partnership request cards. The action and the request ID follow it,
+/// separated by a colon.
+pub const REQUEST_BUTTON_PREFIX: &str = "partner_request:";
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command used outside of a guild");
+	};
+
+	let mut invite_link = "";
+	let mut our_invite_link = "";
+	for option in options.iter() {
+		let ResolvedValue::String(value) = option.value else {
+			bail!("Non-string value passed to a string option");
+		};
+		match option.name {
+			"invite_link" => invite_link = value,
+			"our_invite_link" => our_invite_link = value,
+			_ => bail!("Invalid option passed to partners request command: {}", option.name),
+		}
+	}
+	ensure!(
+		!invite_link.is_empty() && !our_invite_link.is_empty(),
+		severity = Severity::Error,
+		"Not all required options passed to partners request command"
+	);
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let categories: Option<Vec<PartnerCategory>> = {
+		let mut db_connection = db_connection.lock().await;
+		let settings: Option<GuildSettings> = guild_settings::table
+			.find(sql_guild_id)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		match settings {
+			Some(_) => Some(
+				partner_categories::table
+					.filter(partner_categories::guild_id.eq(sql_guild_id))
+					.load(&mut *db_connection)
+					.into_diagnostic()?,
+			),
+			None => None,
+		}
+	};
+	let Some(categories) = categories else {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP);
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	};
+	if categories.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You have no categories to which to add this partner; see `/partner_categories` to create them.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let (their_invite, our_invite) = match (
+		resolve_partner_invite(ctx, invite_link).await,
+		resolve_partner_invite(ctx, our_invite_link).await,
+	) {
+		(Ok(their_invite), Ok(our_invite)) => (their_invite, our_invite),
+		(Err(reason), _) | (_, Err(reason)) => {
+			let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reason);
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+	};
+	let sql_their_guild_id = their_invite.guild.id.get() as i64;
+
+	let problem = {
+		let mut db_connection = db_connection.lock().await;
+		request_problem(&mut db_connection, sql_guild_id, sql_their_guild_id)?
+	};
+	let problem = if our_invite.guild.id != guild {
+		Some("The invite for your server must be an invite to this server.")
+	} else if their_invite.guild.id == guild {
+		Some("You can't partner with your own server.")
+	} else {
+		problem
+	};
+	if let Some(problem) = problem {
+		let message = CreateInteractionResponseMessage::new().ephemeral(true).content(problem);
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let category_select_id = cuid2::create_id();
+	let submit_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+
+	let category_select_options: Vec<CreateSelectMenuOption> = categories
+		.iter()
+		.map(|category| CreateSelectMenuOption::new(&category.name, &category.id))
+		.collect();
+	let category_select = CreateSelectMenu::new(
+		&category_select_id,
+		CreateSelectMenuKind::String {
+			options: category_select_options,
+		},
+	)
+	.placeholder("Partner Category");
+	let submit_button = CreateButton::new(&submit_button_id)
+		.label("Send Request")
+		.style(ButtonStyle::Primary);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(format!(
+			"Choose the category to add {} to if they accept:",
+			their_invite.guild.name
+		))
+		.components(vec![
+			CreateActionRow::SelectMenu(category_select),
+			CreateActionRow::Buttons(vec![submit_button, cancel_button]),
+		])
+		.allowed_mentions(CreateAllowedMentions::new());
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let mut partner_category = String::new();
+	let interaction: ComponentInteraction = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![
+				category_select_id.clone(),
+				submit_button_id.clone(),
+				cancel_button_id.clone(),
+			])
+			.timeout(Duration::from_secs(60))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("Selection timed out; no request was sent.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				partner_category = values.first().cloned().unwrap_or_default();
+				interaction
+					.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+					.await
+					.into_diagnostic()?;
+			}
+			ComponentInteractionDataKind::Button => {
+				if interaction.data.custom_id == submit_button_id {
+					break interaction;
+				}
+				let message = CreateInteractionResponseMessage::new()
+					.ephemeral(true)
+					.content("No request was sent.");
+				interaction
+					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+					.await
+					.into_diagnostic()?;
+				return Ok(());
+			}
+			_ => bail!(
+				"Unexpected interaction type received by partners request command: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	if partner_category.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("No request was sent; a partner category must be selected.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let request = PartnershipRequest {
+		id: cuid2::create_id(),
+		requesting_guild: sql_guild_id,
+		requested_guild: sql_their_guild_id,
+		requesting_category: partner_category,
+		requester: command.user.id.get() as i64,
+		requesting_guild_name: our_invite.guild.name.clone(),
+		requesting_invite_code: our_invite.code.clone(),
+		requested_guild_name: their_invite.guild.name.clone(),
+		requested_invite_code: their_invite.code.clone(),
+	};
+	let request_id = request.id.clone();
+	let insert_result = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::insert_into(partnership_requests::table)
+			.values(request)
+			.execute(&mut *db_connection)
+	};
+	match insert_result {
+		Ok(_) => (),
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("A partnership request to that server is already pending.");
+			interaction
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+		Err(DbError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("The category you selected is no longer valid.");
+			interaction
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+		Err(error) => bail!(error),
+	}
+
+	let card = CreateEmbed::new()
+		.title("Partnership request")
+		.description(format!(
+			"{} would like to partner with this server.",
+			our_invite.guild.name
+		))
+		.field("Requested by", format!("<@{}>", command.user.id.get()), true)
+		.field("Invite", format!("https://discord.gg/{}", our_invite.code), true);
+	let accept_button = CreateButton::new(format!("{}accept:{}", REQUEST_BUTTON_PREFIX, request_id))
+		.label("Accept")
+		.style(ButtonStyle::Success);
+	let decline_button = CreateButton::new(format!("{}decline:{}", REQUEST_BUTTON_PREFIX, request_id))
+		.label("Decline")
+		.style(ButtonStyle::Danger);
+	let components = vec![CreateActionRow::Buttons(vec![accept_button, decline_button])];
+	// The other server may have been reset or purged since its invite was checked, leaving no log channel to send to
+	let delivered = match deliver_staff_log_with_components(ctx, their_invite.guild.id, card, components).await {
+		Ok(sent_message) => sent_message.is_some(),
+		Err(error) => {
+			eprintln!(
+				"Failed to deliver partnership request to guild {}: {:?}",
+				their_invite.guild.id, error
+			);
+			false
+		}
+	};
+
+	if !delivered {
+		{
+			let mut db_connection = db_connection.lock().await;
+			diesel::delete(partnership_requests::table)
+				.filter(partnership_requests::id.eq(&request_id))
+				.execute(&mut *db_connection)
+				.into_diagnostic()?;
+		}
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("The request couldn't be delivered to that server's staff log channel. Ask their staff to check that the bot can post in it.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.content(format!(
+			"Sent a partnership request to {}. Their staff will be able to accept or decline it.",
+			their_invite.guild.name
+		))
+		.allowed_mentions(CreateAllowedMentions::new());
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let embed = log_embed(LogKind::Changed, "Partnership requested", Some(command.user.id)).field(
+		"Server",
+		&their_invite.guild.name,
+		true,
+	);
+	send_staff_log(ctx, guild, embed).await?;
+
+	Ok(())
+}
+
+/// Checks whether a partnership request can be sent from one guild to another. Returns the reason to show to the user
+/// if it can't.
+fn request_problem(
+	db_connection: &mut PgConnection,
+	sql_guild_id: i64,
+	sql_their_guild_id: i64,
+) -> miette::Result<Option<&'static str>> {
+	let their_settings: Option<GuildSettings> = guild_settings::table
+		.find(sql_their_guild_id)
+		.first(db_connection)
+		.optional()
+		.into_diagnostic()?;
+	let Some(their_settings) = their_settings.filter(|settings| settings.departed_at.is_none()) else {
+		return Ok(Some(
+			"That server doesn't use this bot, so it can't receive requests. Use `/partners add` instead.",
+		));
+	};
+	if their_settings.log_channel.is_none() {
+		return Ok(Some(
+			"That server hasn't set up a staff log channel, so it can't receive requests.",
+		));
+	}
+
+	let already_partnered = |guild: i64, partner_guild: i64, db_connection: &mut PgConnection| {
+		partners::table
+			.filter(partners::guild.eq(guild).and(partners::partner_guild.eq(partner_guild)))
+			.count()
+			.get_result::<i64>(db_connection)
+			.into_diagnostic()
+	};
+	if already_partnered(sql_guild_id, sql_their_guild_id, db_connection)? > 0 {
+		return Ok(Some("That server is already a partner."));
+	}
+	if already_partnered(sql_their_guild_id, sql_guild_id, db_connection)? > 0 {
+		return Ok(Some(
+			"That server already lists you as a partner. Use `/partners add` to add them to your list.",
+		));
+	}
+
+	let reverse_request_count: i64 = partnership_requests::table
+		.filter(
+			partnership_requests::requesting_guild
+				.eq(sql_their_guild_id)
+				.and(partnership_requests::requested_guild.eq(sql_guild_id)),
+		)
+		.count()
+		.get_result(db_connection)
+		.into_diagnostic()?;
+	if reverse_request_count > 0 {
+		return Ok(Some(
+			"That server has already requested to partner with you; check your staff log channel to respond.",
+		));
+	}
+
+	Ok(None)
+}
+
+/// Handles the Accept and Decline buttons on a partnership request card
+pub async fn handle_request_button(ctx: &Context, component: &ComponentInteraction) -> miette::Result<()> {
+	let Some(guild) = component.guild_id else {
+		bail!("Partnership request button was used outside of a guild");
+	};
+	let Some((action, request_id)) = component
+		.data
+		.custom_id
+		.strip_prefix(REQUEST_BUTTON_PREFIX)
+		.and_then(|button| button.split_once(':'))
+	else {
+		bail!(
+			"Unexpected custom ID for partnership request button: {}",
+			component.data.custom_id
+		);
+	};
+
+	let can_manage = component
+		.member
+		.as_ref()
+		.and_then(|member| member.permissions)
+		.is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD));
+	if !can_manage {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You need the Manage Server permission to respond to partnership requests.");
+		component
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (request, categories) = {
+		let mut db_connection = db_connection.lock().await;
+		let request: Option<PartnershipRequest> = partnership_requests::table
+			.filter(
+				partnership_requests::id
+					.eq(request_id)
+					.and(partnership_requests::requested_guild.eq(sql_guild_id)),
+			)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let categories: Vec<PartnerCategory> = partner_categories::table
+			.filter(partner_categories::guild_id.eq(sql_guild_id))
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		(request, categories)
+	};
+	let Some(request) = request else {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("This request is no longer pending.");
+		component
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	};
+	let requesting_guild = GuildId::new(request.requesting_guild as u64);
+
+	match action {
+		"accept" => (),
+		"decline" => {
+			{
+				let mut db_connection = db_connection.lock().await;
+				diesel::delete(partnership_requests::table)
+					.filter(partnership_requests::id.eq(&request.id))
+					.execute(&mut *db_connection)
+					.into_diagnostic()?;
+			}
+			let message = CreateInteractionResponseMessage::new()
+				.content(format!("Declined by <@{}>.", component.user.id.get()))
+				.components(Vec::new())
+				.allowed_mentions(CreateAllowedMentions::new());
+			component
+				.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+				.await
+				.into_diagnostic()?;
+
+			let embed = log_embed(LogKind::Removed, "Partnership request declined", None).field(
+				"Server",
+				&request.requested_guild_name,
+				true,
+			);
+			send_staff_log(ctx, requesting_guild, embed).await?;
+			return Ok(());
+		}
+		_ => bail!("Unexpected partnership request button action: {}", action),
+	}
+
+	if categories.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You have no categories to which to add this partner; see `/partner_categories` to create them.");
+		component
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let category_select_id = cuid2::create_id();
+	let submit_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+
+	let category_select_options: Vec<CreateSelectMenuOption> = categories
+		.iter()
+		.map(|category| CreateSelectMenuOption::new(&category.name, &category.id))
+		.collect();
+	let category_select = CreateSelectMenu::new(
+		&category_select_id,
+		CreateSelectMenuKind::String {
+			options: category_select_options,
+		},
+	)
+	.placeholder("Partner Category");
+	let submit_button = CreateButton::new(&submit_button_id)
+		.label("Accept")
+		.style(ButtonStyle::Success);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(format!(
+			"Choose the category to add {} to:",
+			request.requesting_guild_name
+		))
+		.components(vec![
+			CreateActionRow::SelectMenu(category_select),
+			CreateActionRow::Buttons(vec![submit_button, cancel_button]),
+		])
+		.allowed_mentions(CreateAllowedMentions::new());
+	component
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let mut partner_category = String::new();
+	let interaction: ComponentInteraction = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![
+				category_select_id.clone(),
+				submit_button_id.clone(),
+				cancel_button_id.clone(),
+			])
+			.timeout(Duration::from_secs(60))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("Selection timed out; the request is still pending.")
+				.components(Vec::new());
+			component.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				partner_category = values.first().cloned().unwrap_or_default();
+				interaction
+					.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+					.await
+					.into_diagnostic()?;
+			}
+			ComponentInteractionDataKind::Button => {
+				if interaction.data.custom_id == submit_button_id {
+					break interaction;
+				}
+				let message = CreateInteractionResponseMessage::new()
+					.ephemeral(true)
+					.content("The request is still pending.");
+				interaction
+					.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+					.await
+					.into_diagnostic()?;
+				return Ok(());
+			}
+			_ => bail!(
+				"Unexpected interaction type received by partnership request acceptance: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	if partner_category.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("The request wasn't accepted; a partner category must be selected.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let (accept_result, announcements) = {
+		let mut db_connection = db_connection.lock().await;
+		let accept_result = accept_request(&mut db_connection, &request, &partner_category, component.user.id);
+		let mut announcements = Vec::new();
+		if let Ok((requesting_partner_id, accepting_partner_id)) = &accept_result {
+			// The partnership is already committed, so a failed announcement is logged rather than failing the accept
+			for (announcing_guild, partner_id) in
+				[(requesting_guild, requesting_partner_id), (guild, accepting_partner_id)]
+			{
+				match build_partner_announcement(
+					&mut db_connection,
+					announcing_guild,
+					AnnouncementKind::Added,
+					partner_id,
+				) {
+					Ok(announcement) => announcements.push(announcement),
+					Err(error) => eprintln!(
+						"Failed to build the announcement for accepted partner {}: {:?}",
+						partner_id, error
+					),
+				}
+			}
+		}
+		(accept_result, announcements)
+	};
+
+	let failure_reason = match accept_result {
+		Ok(_) => None,
+		Err(DbError::NotFound) => Some("This request is no longer pending."),
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, violation_info)) => {
+			match violation_info.constraint_name() {
+				Some("unique_partner_guild") => Some("One of the servers already lists the other as a partner."),
+				Some("unique_partner_display_name") => Some(
+					"One of the servers already has a partner with the same name. Rename it and accept the request again.",
+				),
+				_ => Some("An unknown collision with another partnership occurred."),
+			}
+		}
+		Err(DbError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+			Some("The category you selected is no longer valid.")
+		}
+		Err(error) => bail!(error),
+	};
+	if let Some(failure_reason) = failure_reason {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(failure_reason);
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(format!("{} is now a partner!", request.requesting_guild_name))
+		.allowed_mentions(CreateAllowedMentions::new());
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+	let card_update = EditMessage::new()
+		.content(format!("Accepted by <@{}>.", component.user.id.get()))
+		.components(Vec::new())
+		.allowed_mentions(CreateAllowedMentions::new());
+	// The card may have been deleted in the meantime, which doesn't affect the partnership
+	let _ = component
+		.channel_id
+		.edit_message(&ctx.http, component.message.id, card_update)
+		.await;
+
+	let embed = log_embed(LogKind::Added, "Partner added", Some(component.user.id))
+		.field("Partner", &request.requesting_guild_name, true)
+		.field("Requested by", format!("<@{}>", request.requester), true);
+	send_staff_log(ctx, guild, embed).await?;
+	let embed = log_embed(LogKind::Added, "Partnership request accepted", None)
+		.field("Partner", &request.requested_guild_name, true)
+		.field("Accepted by", format!("<@{}>", component.user.id.get()), true);
+	send_staff_log(ctx, requesting_guild, embed).await?;
+	for announcement in announcements.into_iter().flatten() {
+		announcement.send(ctx).await?;
+	}
+
+	for partner_guild in [requesting_guild, guild] {
+		let partner_role: Option<Option<i64>> = {
+			let mut db_connection = db_connection.lock().await;
+			guild_settings::table
+				.find(partner_guild.get() as i64)
+				.select(guild_settings::partner_role)
+				.first(&mut *db_connection)
+				.optional()
+				.into_diagnostic()?
+		};
+		if let Some(Some(partner_role)) = partner_role {
+			sync_role_for_guild(ctx, partner_guild, RoleId::new(partner_role as u64)).await?;
+		}
+		queue_embed_update(ctx, partner_guild).await;
+	}
+
+	Ok(())
+}
+
+/// Creates the partners on both sides of an accepted request, registering the requester and the accepting user as
+/// representatives, and removes the request. Returns the partnership IDs for the requesting and accepting guilds.
+fn accept_request(
+	db_connection: &mut PgConnection,
+	request: &PartnershipRequest,
+	accepting_category: &str,
+	accepting_user: UserId,
+) -> QueryResult<(String, String)> {
+	db_connection.transaction(|db_connection| {
+		// Deleting the request first makes sure it can only be accepted once
+		let deleted_count = diesel::delete(partnership_requests::table)
+			.filter(partnership_requests::id.eq(&request.id))
+			.execute(db_connection)?;
+		if deleted_count == 0 {
+			return Err(DbError::NotFound);
+		}
+
+		let accepting_user = accepting_user.get() as i64;
+		let requesting_partner = Partner {
+			partnership_id: cuid2::create_id(),
+			guild: request.requesting_guild,
+			category: request.requesting_category.clone(),
+			partner_guild: request.requested_guild,
+			display_name: request.requested_guild_name.clone(),
+			invite_code: request.requested_invite_code.clone(),
+			description: String::new(),
+			emoji: String::new(),
+			status: PartnerStatus::Active.as_str().to_string(),
+			partner_guild_name: request.requested_guild_name.clone(),
+			member_count: None,
+		};
+		let accepting_partner = Partner {
+			partnership_id: cuid2::create_id(),
+			guild: request.requested_guild,
+			category: accepting_category.to_string(),
+			partner_guild: request.requesting_guild,
+			display_name: request.requesting_guild_name.clone(),
+			invite_code: request.requesting_invite_code.clone(),
+			description: String::new(),
+			emoji: String::new(),
+			status: PartnerStatus::Active.as_str().to_string(),
+			partner_guild_name: request.requesting_guild_name.clone(),
+			member_count: None,
+		};
+		let requesting_partner_id = requesting_partner.partnership_id.clone();
+		let accepting_partner_id = accepting_partner.partnership_id.clone();
+
+		// Each side's user represents their own server to the other side
+		let reps = vec![
+			PartnerUser {
+				partnership_id: requesting_partner_id.clone(),
+				user_id: accepting_user,
+			},
+			PartnerUser {
+				partnership_id: accepting_partner_id.clone(),
+				user_id: request.requester,
+			},
+		];
+		let self_reps = vec![
+			PartnerSelfUser {
+				partnership: requesting_partner_id.clone(),
+				user_id: request.requester,
+			},
+			PartnerSelfUser {
+				partnership: accepting_partner_id.clone(),
+				user_id: accepting_user,
+			},
+		];
+
+		diesel::insert_into(partners::table)
+			.values(vec![requesting_partner, accepting_partner])
+			.execute(db_connection)?;
+		diesel::insert_into(partner_users::table)
+			.values(reps)
+			.execute(db_connection)?;
+		diesel::insert_into(partner_self_users::table)
+			.values(self_reps)
+			.execute(db_connection)?;
+
+		Ok((requesting_partner_id, accepting_partner_id))
+	})
+}
@@ -0,0 +1,308 @@
+use crate::database::get_database_connection;
+use crate::models::{ArchivedPartner, GuildSettings, PartnerCategory};
+use crate::schema::{archived_partners, guild_settings, partner_categories};
+use crate::sync::announcement::{build_partner_announcement, AnnouncementKind};
+use crate::sync::embed_queue::queue_embed_update;
+use crate::sync::role::sync_role_for_guild;
+use crate::utils::pagination::{get_archived_partners_for_page, max_partner_page};
+use crate::utils::partner_archive::restore_partner;
+use crate::utils::partner_status::PartnerStatus;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DbError};
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+	CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind,
+};
+use serenity::model::id::RoleId;
+use std::time::Duration;
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command used outside of a guild");
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (partner_role, archived, categories) = {
+		let mut db_connection = db_connection.lock().await;
+
+		let guild_settings: Option<GuildSettings> = guild_settings::table
+			.find(sql_guild_id)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let Some(guild_settings) = guild_settings else {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(GUILD_NOT_SET_UP);
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		};
+		let partner_role = guild_settings.partner_role.map(|role| RoleId::new(role as u64));
+
+		let archived: Vec<ArchivedPartner> = archived_partners::table
+			.filter(archived_partners::guild.eq(sql_guild_id))
+			.order(archived_partners::display_name.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let categories: Vec<PartnerCategory> = partner_categories::table
+			.filter(partner_categories::guild_id.eq(sql_guild_id))
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+
+		(partner_role, archived, categories)
+	};
+
+	if archived.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("There are no removed partners to restore.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+	if categories.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You have no categories to which to restore a partner; see `/partner_categories` to create them.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let mut current_partner_page = 0;
+	let mut partner_id = String::new();
+	let mut category_id = String::new();
+
+	let partner_select_id = cuid2::create_id();
+	let category_select_id = cuid2::create_id();
+	let submit_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+
+	let submit_button = CreateButton::new(&submit_button_id)
+		.label("Restore")
+		.style(ButtonStyle::Primary);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+	let buttons_row = CreateActionRow::Buttons(vec![submit_button, cancel_button]);
+
+	let select_rows = |page: usize, partner_id: &str, category_id: &str| -> Vec<CreateActionRow> {
+		let partner_select = CreateSelectMenu::new(
+			&partner_select_id,
+			CreateSelectMenuKind::String {
+				options: get_archived_partners_for_page(&archived, page, partner_id),
+			},
+		)
+		.placeholder("Removed Partner");
+		let category_select_options: Vec<CreateSelectMenuOption> = categories
+			.iter()
+			.map(|category| {
+				CreateSelectMenuOption::new(&category.name, &category.id).default_selection(category.id == category_id)
+			})
+			.collect();
+		let category_select = CreateSelectMenu::new(
+			&category_select_id,
+			CreateSelectMenuKind::String {
+				options: category_select_options,
+			},
+		)
+		.placeholder("Partner Category");
+		vec![
+			CreateActionRow::SelectMenu(partner_select),
+			CreateActionRow::SelectMenu(category_select),
+			buttons_row.clone(),
+		]
+	};
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Select the partner to restore and the category to restore it to:")
+		.components(select_rows(current_partner_page, &partner_id, &category_id));
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let interaction: ComponentInteraction = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![
+				partner_select_id.clone(),
+				category_select_id.clone(),
+				submit_button_id.clone(),
+				cancel_button_id.clone(),
+			])
+			.timeout(Duration::from_secs(60))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("No partner was restored.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				let value = values.first().cloned().unwrap_or_default();
+				interaction
+					.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+					.await
+					.into_diagnostic()?;
+				if interaction.data.custom_id == category_select_id {
+					category_id = value;
+					continue;
+				}
+				if value == "<" {
+					current_partner_page = current_partner_page.saturating_sub(1);
+				} else if value == ">" {
+					current_partner_page = (current_partner_page + 1).min(max_partner_page(&archived));
+				} else {
+					// Default to the category the partner was in, if it still exists
+					let original_category = archived
+						.iter()
+						.find(|partner| partner.partnership_id == value)
+						.and_then(|partner| partner.category.clone());
+					partner_id = value;
+					match original_category {
+						Some(original_category) if category_id.is_empty() => category_id = original_category,
+						_ => continue,
+					}
+				}
+
+				let message = EditInteractionResponse::new().components(select_rows(
+					current_partner_page,
+					&partner_id,
+					&category_id,
+				));
+				command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			}
+			ComponentInteractionDataKind::Button => {
+				if interaction.data.custom_id == submit_button_id {
+					break interaction;
+				}
+				if interaction.data.custom_id == cancel_button_id {
+					let message = CreateInteractionResponseMessage::new()
+						.ephemeral(true)
+						.content("No partner was restored.");
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+						.await
+						.into_diagnostic()?;
+					return Ok(());
+				}
+			}
+			_ => bail!(
+				"Unexpected interaction type received by partners restore command: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	let failure_reason = if partner_id.is_empty() {
+		Some("No partner was restored; the partner to restore was not selected.")
+	} else if category_id.is_empty() {
+		Some("No partner was restored; a category must be selected.")
+	} else {
+		None
+	};
+	if let Some(failure_reason) = failure_reason {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(failure_reason);
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let Some(archived_partner) = archived.iter().find(|partner| partner.partnership_id == partner_id) else {
+		bail!("Partner selection desynchronized with archived partner list");
+	};
+	let status: PartnerStatus = archived_partner.status.parse()?;
+
+	let (restore_result, announcement) = {
+		let mut db_connection = db_connection.lock().await;
+		let restore_result = restore_partner(&mut db_connection, &partner_id, &category_id);
+		let announcement = if restore_result.is_ok() && status.is_listed() {
+			build_partner_announcement(&mut db_connection, guild, AnnouncementKind::Added, &partner_id)?
+		} else {
+			None
+		};
+		(restore_result, announcement)
+	};
+
+	let restored = restore_result.is_ok();
+	let message = match restore_result {
+		Ok(_) => CreateInteractionResponseMessage::new()
+			.content(format!(
+				"Restored {} as a partner, along with its representatives.",
+				archived_partner.display_name
+			))
+			.allowed_mentions(CreateAllowedMentions::new()),
+		Err(DbError::NotFound) => CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("That partner has already been restored."),
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, violation_info)) => {
+			let content = match violation_info.constraint_name() {
+				Some("unique_partner_guild") => {
+					String::from("That server has been added as a partner again since it was removed.")
+				}
+				Some("unique_partner_display_name") => format!(
+					"Another partner is now named {}. Rename it with `/partners set_name` before restoring this partner.",
+					archived_partner.display_name
+				),
+				_ => String::from("An unknown collision with another partnership occurred."),
+			};
+			CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(content)
+				.allowed_mentions(CreateAllowedMentions::new())
+		}
+		Err(DbError::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, _)) => {
+			CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("The category you selected is no longer valid.")
+		}
+		Err(error) => bail!(error),
+	};
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+	if !restored {
+		return Ok(());
+	}
+
+	let embed = log_embed(LogKind::Added, "Partner restored", Some(command.user.id))
+		.field("Partner", &archived_partner.display_name, true)
+		.field("Status", status.display_name(), true);
+	send_staff_log(ctx, guild, embed).await?;
+	if let Some(announcement) = announcement {
+		announcement.send(ctx).await?;
+	}
+
+	if let (Some(partner_role), true) = (partner_role, status.grants_role()) {
+		sync_role_for_guild(ctx, guild, partner_role).await?;
+	}
+
+	queue_embed_update(ctx, guild).await;
+
+	Ok(())
+}
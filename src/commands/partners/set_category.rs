@@ -3,6 +3,7 @@ use crate::models::{Partner, PartnerCategory};
 use crate::schema::{partner_categories, partners};
 use crate::utils::pagination::{get_partners_for_page, max_partner_page};
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use miette::{bail, IntoDiagnostic};
 use serenity::builder::{
@@ -198,12 +199,14 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		bail!("Partner category selection desynchronized from partner category list");
 	};
 
-	let mut db_connection = db_connection.lock().await;
-	diesel::update(partners::table)
-		.filter(partners::partnership_id.eq(&partner_id))
-		.set(partners::category.eq(&category_id))
-		.execute(&mut *db_connection)
-		.into_diagnostic()?;
+	{
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(partners::table)
+			.filter(partners::partnership_id.eq(&partner_id))
+			.set(partners::category.eq(&category_id))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?;
+	}
 
 	let message = CreateInteractionResponseMessage::new().content(format!(
 		"Updated the category of {} to {}.",
@@ -214,5 +217,21 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		.await
 		.into_diagnostic()?;
 
+	let old_category_name = partners
+		.iter()
+		.find(|partner| partner.partnership_id == partner_id)
+		.and_then(|partner| {
+			partner_categories
+				.iter()
+				.find(|category| category.id == partner.category)
+		})
+		.map(|category| category.name.clone())
+		.unwrap_or_default();
+	let embed = log_embed(LogKind::Changed, "Partner category changed", Some(command.user.id))
+		.field("Partner", partner_display_name, true)
+		.field("Old category", old_category_name, true)
+		.field("New category", category_name, true);
+	send_staff_log(ctx, guild, embed).await?;
+
 	Ok(())
 }
@@ -0,0 +1,275 @@
+use crate::database::get_database_connection;
+use crate::models::Partner;
+use crate::schema::partners;
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use diesel::result::Error as DbError;
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{
+	CreateActionRow, CreateButton, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
+	CreateSelectMenu, CreateSelectMenuKind, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, InputTextStyle,
+};
+use serenity::utils::{parse_emoji, CreateQuickModal};
+use std::time::Duration;
+
+/// The longest unicode emoji sequences (such as family or flag sequences) fit within this many characters
+const MAX_UNICODE_EMOJI_LENGTH: usize = 16;
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command used outside of a guild");
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let partners: Vec<Partner> = {
+		let mut db_connection = db_connection.lock().await;
+		if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+			return Ok(());
+		}
+
+		partners::table
+			.filter(partners::guild.eq(sql_guild_id))
+			.order(partners::display_name.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	if partners.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You have no partners for which to set details.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let mut current_partner_page = 0;
+	let partner_select_options = get_partners_for_page(&partners, current_partner_page, "");
+
+	let partner_select_id = cuid2::create_id();
+	let submit_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+
+	let partner_select = CreateSelectMenu::new(
+		&partner_select_id,
+		CreateSelectMenuKind::String {
+			options: partner_select_options,
+		},
+	)
+	.placeholder("Partner");
+	let submit_button = CreateButton::new(&submit_button_id)
+		.label("Edit details")
+		.style(ButtonStyle::Primary);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+
+	let partner_row = CreateActionRow::SelectMenu(partner_select);
+	let buttons_row = CreateActionRow::Buttons(vec![submit_button, cancel_button]);
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Select the partner for which to set details:")
+		.components(vec![partner_row, buttons_row.clone()]);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let mut partner_id = String::new();
+
+	let interaction: ComponentInteraction = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![
+				partner_select_id.clone(),
+				submit_button_id.clone(),
+				cancel_button_id.clone(),
+			])
+			.timeout(Duration::from_secs(30))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("No partner details were updated.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				let value = values.first().cloned().unwrap_or_default();
+				if interaction.data.custom_id == partner_select_id {
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+						.await
+						.into_diagnostic()?;
+					if value == "<" {
+						current_partner_page = current_partner_page.saturating_sub(1);
+					} else if value == ">" {
+						current_partner_page = (current_partner_page + 1).min(max_partner_page(&partners));
+					} else {
+						partner_id = value;
+						continue;
+					}
+
+					let partner_select_options = get_partners_for_page(&partners, current_partner_page, &partner_id);
+					let partner_select = CreateSelectMenu::new(
+						&partner_select_id,
+						CreateSelectMenuKind::String {
+							options: partner_select_options,
+						},
+					)
+					.placeholder("Partner");
+					let partner_row = CreateActionRow::SelectMenu(partner_select);
+
+					let message = EditInteractionResponse::new().components(vec![partner_row, buttons_row.clone()]);
+					command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+				}
+			}
+			ComponentInteractionDataKind::Button => {
+				if interaction.data.custom_id == submit_button_id {
+					break interaction;
+				}
+				if interaction.data.custom_id == cancel_button_id {
+					let message = CreateInteractionResponseMessage::new()
+						.ephemeral(true)
+						.content("No partner details were updated.");
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+						.await
+						.into_diagnostic()?;
+					return Ok(());
+				}
+			}
+			_ => bail!(
+				"Unexpected interaction type encountered with partners set_details command: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	let Some(partner) = partners.iter().find(|partner| partner.partnership_id == partner_id) else {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("No partner details were updated; the partner to update was not selected.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	};
+
+	let mut description_input = CreateInputText::new(InputTextStyle::Short, "Description", "")
+		.placeholder("A short, one-line description of the server")
+		.max_length(100)
+		.required(false);
+	if !partner.description.is_empty() {
+		description_input = description_input.value(&partner.description);
+	}
+	let mut emoji_input = CreateInputText::new(InputTextStyle::Short, "Emoji", "")
+		.placeholder("A unicode emoji or a custom emoji like <:name:id>")
+		.max_length(100)
+		.required(false);
+	if !partner.emoji.is_empty() {
+		emoji_input = emoji_input.value(&partner.emoji);
+	}
+
+	let modal = CreateQuickModal::new("Partner Details")
+		.timeout(Duration::from_secs(600))
+		.field(description_input)
+		.field(emoji_input);
+	let modal_response = interaction.quick_modal(ctx, modal).await.into_diagnostic()?;
+
+	let Some(modal_response) = modal_response else {
+		let message = EditInteractionResponse::new()
+			.content("No partner details were updated.")
+			.components(Vec::new());
+		command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+		return Ok(());
+	};
+	let mut inputs_iter = modal_response.inputs.into_iter();
+	let description = inputs_iter.next().unwrap_or_default().trim().to_string();
+	let emoji = inputs_iter.next().unwrap_or_default().trim().to_string();
+
+	if !emoji_is_valid(&emoji) {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("The entered emoji isn't a valid emoji.");
+		modal_response
+			.interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let partner_update_result = {
+		let mut db_connection = db_connection.lock().await;
+		let partner_update_result: QueryResult<Partner> = diesel::update(partners::table)
+			.filter(partners::partnership_id.eq(&partner.partnership_id))
+			.set((partners::description.eq(&description), partners::emoji.eq(&emoji)))
+			.get_result(&mut *db_connection);
+		partner_update_result
+	};
+
+	let mut log_entry = None;
+	let message = match partner_update_result {
+		Ok(updated_partner) => {
+			let shown_description = if description.is_empty() {
+				String::from("None")
+			} else {
+				description.clone()
+			};
+			let shown_emoji = if emoji.is_empty() {
+				String::from("None")
+			} else {
+				emoji.clone()
+			};
+			log_entry = Some(
+				log_embed(LogKind::Changed, "Partner details changed", Some(command.user.id))
+					.field("Partner", &updated_partner.display_name, true)
+					.field("Emoji", &shown_emoji, true)
+					.field("Description", &shown_description, false),
+			);
+			CreateInteractionResponseMessage::new()
+				.content(format!("Updated the details for {}.", updated_partner.display_name))
+		}
+		Err(DbError::NotFound) => CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("That server is no longer a partner."),
+		Err(error) => bail!(error),
+	};
+	modal_response
+		.interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	if let Some(embed) = log_entry {
+		send_staff_log(ctx, guild, embed).await?;
+		queue_embed_update(ctx, guild).await;
+	}
+
+	Ok(())
+}
+
+/// Checks that an entered emoji is either blank, a custom emoji, or plausibly a unicode emoji
+fn emoji_is_valid(emoji: &str) -> bool {
+	if emoji.is_empty() || parse_emoji(emoji).is_some() {
+		return true;
+	}
+	emoji.chars().count() <= MAX_UNICODE_EMOJI_LENGTH
+		&& !emoji
+			.chars()
+			.any(|c| c.is_ascii_alphanumeric() || c.is_whitespace() || c == '<' || c == '>')
+}
@@ -0,0 +1,293 @@
+use crate::database::get_database_connection;
+use crate::models::Partner;
+use crate::schema::partners;
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::invite::resolve_partner_invite;
+use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DbError};
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+	CreateSelectMenu, CreateSelectMenuKind, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, ResolvedOption, ResolvedValue,
+};
+use serenity::model::permissions::Permissions;
+use std::time::Duration;
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command used outside of a guild");
+	};
+
+	let mut invite_link = "";
+	let mut allow_server_change = false;
+	for option in options.iter() {
+		match (option.name, &option.value) {
+			("invite_link", ResolvedValue::String(value)) => invite_link = value,
+			("allow_server_change", ResolvedValue::Boolean(value)) => allow_server_change = *value,
+			_ => bail!("Invalid option passed to partners set_invite command: {:?}", option),
+		}
+	}
+	ensure!(
+		!invite_link.is_empty(),
+		severity = Severity::Error,
+		"Not all required options passed to partners set_invite command"
+	);
+
+	// Moving a partnership to a different server is a bigger change than rotating an invite, so it's limited to
+	// administrators
+	let is_admin = command
+		.member
+		.as_ref()
+		.and_then(|member| member.permissions)
+		.is_some_and(|permissions| permissions.contains(Permissions::ADMINISTRATOR));
+	if allow_server_change && !is_admin {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("Only administrators can move a partner to a different server.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let partners: Vec<Partner> = {
+		let mut db_connection = db_connection.lock().await;
+		if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+			return Ok(());
+		}
+
+		partners::table
+			.filter(partners::guild.eq(sql_guild_id))
+			.order(partners::display_name.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	if partners.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You have no partners for which to set the invite.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let new_invite = match resolve_partner_invite(ctx, invite_link).await {
+		Ok(invite) => invite,
+		Err(reason) => {
+			let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reason);
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+	};
+	let new_partner_guild = new_invite.guild.id.get() as i64;
+
+	let mut current_partner_page = 0;
+	let partner_select_options = get_partners_for_page(&partners, current_partner_page, "");
+
+	let partner_select_id = cuid2::create_id();
+	let submit_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+
+	let partner_select = CreateSelectMenu::new(
+		&partner_select_id,
+		CreateSelectMenuKind::String {
+			options: partner_select_options,
+		},
+	)
+	.placeholder("Partner");
+	let submit_button = CreateButton::new(&submit_button_id)
+		.label("Update")
+		.style(ButtonStyle::Primary);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+
+	let partner_row = CreateActionRow::SelectMenu(partner_select);
+	let buttons_row = CreateActionRow::Buttons(vec![submit_button, cancel_button]);
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Select the partner whose invite to change:")
+		.components(vec![partner_row, buttons_row.clone()]);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let mut partner_id = String::new();
+
+	let interaction: ComponentInteraction = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![
+				partner_select_id.clone(),
+				submit_button_id.clone(),
+				cancel_button_id.clone(),
+			])
+			.timeout(Duration::from_secs(30))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("No invite was updated.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				let value = values.first().cloned().unwrap_or_default();
+				if interaction.data.custom_id == partner_select_id {
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+						.await
+						.into_diagnostic()?;
+					if value == "<" {
+						current_partner_page = current_partner_page.saturating_sub(1);
+					} else if value == ">" {
+						current_partner_page = (current_partner_page + 1).min(max_partner_page(&partners));
+					} else {
+						partner_id = value;
+						continue;
+					}
+
+					let partner_select_options = get_partners_for_page(&partners, current_partner_page, &partner_id);
+					let partner_select = CreateSelectMenu::new(
+						&partner_select_id,
+						CreateSelectMenuKind::String {
+							options: partner_select_options,
+						},
+					)
+					.placeholder("Partner");
+					let partner_row = CreateActionRow::SelectMenu(partner_select);
+
+					let message = EditInteractionResponse::new().components(vec![partner_row, buttons_row.clone()]);
+					command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+				}
+			}
+			ComponentInteractionDataKind::Button => {
+				if interaction.data.custom_id == submit_button_id {
+					break interaction;
+				}
+				if interaction.data.custom_id == cancel_button_id {
+					let message = CreateInteractionResponseMessage::new()
+						.ephemeral(true)
+						.content("No invite was updated.");
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+						.await
+						.into_diagnostic()?;
+					return Ok(());
+				}
+			}
+			_ => bail!(
+				"Unexpected interaction type encountered with partners set_invite command: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	if partner_id.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("No invite was updated; the partner to update was not selected.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let Some(partner) = partners.iter().find(|partner| partner.partnership_id == partner_id) else {
+		bail!("Partner selection desynchronized with partner list");
+	};
+	let server_changed = partner.partner_guild != new_partner_guild;
+	if server_changed && !allow_server_change {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(format!(
+				"That invite is for {}, which isn't the server partnered as {}. If the partner moved to a new server, an administrator can set the invite with `allow_server_change`.",
+				new_invite.guild.name, partner.display_name
+			))
+			.allowed_mentions(CreateAllowedMentions::new());
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let update_result: QueryResult<usize> = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(partners::table)
+			.filter(partners::partnership_id.eq(&partner_id))
+			.set((
+				partners::invite_code.eq(&new_invite.code),
+				partners::partner_guild.eq(new_partner_guild),
+				partners::partner_guild_name.eq(&new_invite.guild.name),
+				partners::member_count.eq(new_invite.member_count),
+			))
+			.execute(&mut *db_connection)
+	};
+
+	let message = match update_result {
+		Ok(0) => CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("That server is no longer a partner."),
+		Ok(_) => CreateInteractionResponseMessage::new()
+			.content(format!(
+				"Updated the invite for {} to https://discord.gg/{}.",
+				partner.display_name, new_invite.code
+			))
+			.allowed_mentions(CreateAllowedMentions::new()),
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(format!("{} is already a partner.", new_invite.guild.name))
+			.allowed_mentions(CreateAllowedMentions::new()),
+		Err(error) => bail!(error),
+	};
+	let updated = matches!(update_result, Ok(count) if count > 0);
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+	if !updated {
+		return Ok(());
+	}
+
+	let mut embed = log_embed(LogKind::Changed, "Partner invite changed", Some(command.user.id))
+		.field("Partner", &partner.display_name, true)
+		.field(
+			"Old invite",
+			format!("https://discord.gg/{}", partner.invite_code),
+			true,
+		)
+		.field("New invite", format!("https://discord.gg/{}", new_invite.code), true);
+	if server_changed {
+		embed = embed.field("New server", &new_invite.guild.name, true);
+	}
+	send_staff_log(ctx, guild, embed).await?;
+
+	queue_embed_update(ctx, guild).await;
+
+	Ok(())
+}
@@ -1,9 +1,10 @@
 use crate::database::get_database_connection;
 use crate::models::Partner;
 use crate::schema::partners;
-use crate::sync::embed::update_embed;
+use crate::sync::embed_queue::queue_embed_update;
 use crate::utils::pagination::{get_partners_for_page, max_partner_page};
 use crate::utils::setup_check::guild_setup_check_with_reply;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use diesel::result::{DatabaseErrorKind, Error as DbError};
 use miette::{bail, ensure, IntoDiagnostic, Severity};
@@ -193,9 +194,22 @@ pub async fn execute(
 		partner_update_result
 	};
 
+	let mut log_entry = None;
 	let message = match partner_update_result {
-		Ok(partner) => CreateInteractionResponseMessage::new()
-			.content(format!("Updated partner name to {}.", partner.display_name)),
+		Ok(partner) => {
+			let old_name = partners
+				.iter()
+				.find(|old_partner| old_partner.partnership_id == partner.partnership_id)
+				.map(|old_partner| old_partner.display_name.clone())
+				.unwrap_or_default();
+			log_entry = Some(
+				log_embed(LogKind::Changed, "Partner renamed", Some(command.user.id))
+					.field("Old name", old_name, true)
+					.field("New name", &partner.display_name, true),
+			);
+			CreateInteractionResponseMessage::new()
+				.content(format!("Updated partner name to {}.", partner.display_name))
+		}
 		Err(DbError::NotFound) => CreateInteractionResponseMessage::new()
 			.ephemeral(true)
 			.content("That server is no longer a partner."),
@@ -209,7 +223,11 @@ pub async fn execute(
 		.await
 		.into_diagnostic()?;
 
-	update_embed(ctx, guild).await?;
+	if let Some(embed) = log_entry {
+		send_staff_log(ctx, guild, embed).await?;
+	}
+
+	queue_embed_update(ctx, guild).await;
 
 	Ok(())
 }
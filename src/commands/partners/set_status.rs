@@ -0,0 +1,313 @@
+use super::remove::archive_and_follow_up;
+use crate::database::get_database_connection;
+use crate::models::{GuildSettings, Partner};
+use crate::schema::{guild_settings, partners};
+use crate::sync::embed_queue::queue_embed_update;
+use crate::sync::role::sync_role_for_guild;
+use crate::utils::pagination::{get_partners_for_page, max_partner_page};
+use crate::utils::partner_status::PartnerStatus;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+	CreateSelectMenu, CreateSelectMenuKind, EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{
+	ButtonStyle, CommandInteraction, ComponentInteraction, ComponentInteractionDataKind, ResolvedOption, ResolvedValue,
+};
+use serenity::model::id::RoleId;
+use std::time::Duration;
+
+/// The status choice that ends a partnership, archiving the partner
+pub const ENDED_STATUS_CHOICE: &str = "ended";
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command used outside of a guild");
+	};
+
+	let mut new_status = "";
+	let mut reason = String::new();
+	for option in options.iter() {
+		let ResolvedValue::String(value) = option.value else {
+			bail!("Non-string value passed to a string option");
+		};
+		match option.name {
+			"status" => new_status = value,
+			"reason" => reason = value.trim().to_string(),
+			_ => bail!("Invalid option passed to partners set_status command: {}", option.name),
+		}
+	}
+	ensure!(
+		!new_status.is_empty(),
+		severity = Severity::Error,
+		"No status passed to partners set_status command"
+	);
+	// Ended partnerships are archived rather than kept with a status
+	let new_status: Option<PartnerStatus> = if new_status == ENDED_STATUS_CHOICE {
+		None
+	} else {
+		Some(new_status.parse()?)
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (partner_role, partners) = {
+		let mut db_connection = db_connection.lock().await;
+
+		let guild_settings: Option<GuildSettings> = guild_settings::table
+			.find(sql_guild_id)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let Some(guild_settings) = guild_settings else {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(GUILD_NOT_SET_UP);
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		};
+		let partner_role = guild_settings.partner_role.map(|role| RoleId::new(role as u64));
+
+		let partners: Vec<Partner> = partners::table
+			.filter(partners::guild.eq(sql_guild_id))
+			.order(partners::display_name.asc())
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+
+		(partner_role, partners)
+	};
+
+	if partners.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You have no partners for which to set the status.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let mut current_partner_page = 0;
+	let partner_select_options = get_partners_for_page(&partners, current_partner_page, "");
+
+	let partner_select_id = cuid2::create_id();
+	let submit_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+
+	let partner_select = CreateSelectMenu::new(
+		&partner_select_id,
+		CreateSelectMenuKind::String {
+			options: partner_select_options,
+		},
+	)
+	.placeholder("Partner");
+	let submit_button = CreateButton::new(&submit_button_id)
+		.label("Update")
+		.style(ButtonStyle::Primary);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+
+	let partner_row = CreateActionRow::SelectMenu(partner_select);
+	let buttons_row = CreateActionRow::Buttons(vec![submit_button, cancel_button]);
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Select the partner whose status to change:")
+		.components(vec![partner_row, buttons_row.clone()]);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let mut partner_id = String::new();
+
+	let interaction: ComponentInteraction = loop {
+		let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+			.custom_ids(vec![
+				partner_select_id.clone(),
+				submit_button_id.clone(),
+				cancel_button_id.clone(),
+			])
+			.timeout(Duration::from_secs(30))
+			.await
+		else {
+			let message = EditInteractionResponse::new()
+				.content("No partner status was changed.")
+				.components(Vec::new());
+			command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+			return Ok(());
+		};
+		match &interaction.data.kind {
+			ComponentInteractionDataKind::StringSelect { values } => {
+				let value = values.first().cloned().unwrap_or_default();
+				if interaction.data.custom_id == partner_select_id {
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Acknowledge)
+						.await
+						.into_diagnostic()?;
+					if value == "<" {
+						current_partner_page = current_partner_page.saturating_sub(1);
+					} else if value == ">" {
+						current_partner_page = (current_partner_page + 1).min(max_partner_page(&partners));
+					} else {
+						partner_id = value;
+						continue;
+					}
+
+					let partner_select_options = get_partners_for_page(&partners, current_partner_page, &partner_id);
+					let partner_select = CreateSelectMenu::new(
+						&partner_select_id,
+						CreateSelectMenuKind::String {
+							options: partner_select_options,
+						},
+					)
+					.placeholder("Partner");
+					let partner_row = CreateActionRow::SelectMenu(partner_select);
+
+					let message = EditInteractionResponse::new().components(vec![partner_row, buttons_row.clone()]);
+					command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+				}
+			}
+			ComponentInteractionDataKind::Button => {
+				if interaction.data.custom_id == submit_button_id {
+					break interaction;
+				}
+				if interaction.data.custom_id == cancel_button_id {
+					let message = CreateInteractionResponseMessage::new()
+						.ephemeral(true)
+						.content("No partner status was changed.");
+					interaction
+						.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+						.await
+						.into_diagnostic()?;
+					return Ok(());
+				}
+			}
+			_ => bail!(
+				"Unexpected interaction type encountered with partners set_status command: {:?}",
+				interaction.data.kind
+			),
+		}
+	};
+
+	if partner_id.is_empty() {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("No partner status was changed; the partner to update was not selected.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let Some(partner) = partners.iter().find(|partner| partner.partnership_id == partner_id) else {
+		bail!("Partner selection desynchronized with partner list");
+	};
+	let old_status: PartnerStatus = partner.status.parse()?;
+
+	let Some(new_status) = new_status else {
+		let reply = format!(
+			"Ended the partnership with {}. It was archived and can be brought back with `/partners restore`.",
+			partner.display_name
+		);
+		let log_entry = log_embed(LogKind::Removed, "Partnership ended", Some(command.user.id))
+			.field("Partner", &partner.display_name, true)
+			.field("Old status", old_status.display_name(), true);
+		return archive_and_follow_up(ctx, &interaction, partner_role, &partner_id, &reason, reply, log_entry).await;
+	};
+
+	if !old_status.can_change_to(new_status) {
+		let content = if old_status == new_status {
+			format!(
+				"{} is already {}.",
+				partner.display_name,
+				new_status.display_name().to_lowercase()
+			)
+		} else {
+			format!(
+				"{} can't go from {} to {}.",
+				partner.display_name,
+				old_status.display_name().to_lowercase(),
+				new_status.display_name().to_lowercase()
+			)
+		};
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(content)
+			.allowed_mentions(CreateAllowedMentions::new());
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let updated_count = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(partners::table)
+			.filter(
+				partners::partnership_id
+					.eq(&partner_id)
+					.and(partners::status.eq(old_status.as_str())),
+			)
+			.set(partners::status.eq(new_status.as_str()))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	if updated_count == 0 {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("That partner was changed or removed while you were choosing. Please try again.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.content(format!(
+			"Changed the status of {} to {}.",
+			partner.display_name,
+			new_status.display_name().to_lowercase()
+		))
+		.allowed_mentions(CreateAllowedMentions::new());
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let embed = log_embed(LogKind::Changed, "Partner status changed", Some(command.user.id))
+		.field("Partner", &partner.display_name, true)
+		.field("Old status", old_status.display_name(), true)
+		.field("New status", new_status.display_name(), true);
+	send_staff_log(ctx, guild, embed).await?;
+
+	if old_status.grants_role() != new_status.grants_role() {
+		if let Some(partner_role) = partner_role {
+			sync_role_for_guild(ctx, guild, partner_role).await?;
+		}
+	}
+
+	if old_status.is_listed() != new_status.is_listed() {
+		queue_embed_update(ctx, guild).await;
+	}
+
+	Ok(())
+}
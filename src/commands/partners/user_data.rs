@@ -0,0 +1,349 @@
+use crate::database::get_database_connection;
+use crate::models::GuildSettings;
+use crate::schema::{
+	archived_partner_self_users, archived_partner_users, archived_partners, guild_settings, partner_role_exempt_users,
+	partner_self_users, partner_users, partners, partnership_requests, pending_partner_changes,
+};
+use crate::sync::role::sync_role_for_guild;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+	EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::collector::ComponentInteractionCollector;
+use serenity::model::application::{ButtonStyle, CommandInteraction, ResolvedOption, ResolvedValue};
+use serenity::model::id::RoleId;
+use std::time::Duration;
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Partners command executed outside of a guild");
+	};
+
+	let Some(user_option) = options.first() else {
+		bail!("Insufficient options passed to partners user_data command");
+	};
+	ensure!(
+		user_option.name == "user",
+		severity = Severity::Error,
+		"Wrong option passed to partners user_data command: {:?}",
+		user_option
+	);
+	let ResolvedValue::User(user, _) = user_option.value else {
+		bail!("Incorrect type provided for user option: {:?}", user_option.value);
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let sql_user_id = user.id.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+
+	let (
+		partner_role,
+		partner_names,
+		partner_self_names,
+		archived_names,
+		requested_names,
+		changed_names,
+		is_role_exempt,
+	) = {
+		let mut db_connection = db_connection.lock().await;
+
+		let settings: Option<GuildSettings> = guild_settings::table
+			.find(sql_guild_id)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let Some(settings) = settings else {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(GUILD_NOT_SET_UP);
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		};
+		let partner_role = settings.partner_role.map(|role| RoleId::new(role as u64));
+
+		let partner_names: Vec<String> = partners::table
+			.filter(
+				partners::guild.eq(sql_guild_id).and(
+					partners::partnership_id.eq_any(
+						partner_users::table
+							.filter(partner_users::user_id.eq(sql_user_id))
+							.select(partner_users::partnership_id),
+					),
+				),
+			)
+			.order(partners::display_name.asc())
+			.select(partners::display_name)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let partner_self_names: Vec<String> = partners::table
+			.filter(
+				partners::guild.eq(sql_guild_id).and(
+					partners::partnership_id.eq_any(
+						partner_self_users::table
+							.filter(partner_self_users::user_id.eq(sql_user_id))
+							.select(partner_self_users::partnership),
+					),
+				),
+			)
+			.order(partners::display_name.asc())
+			.select(partners::display_name)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let archived_names: Vec<String> = archived_partners::table
+			.filter(
+				archived_partners::guild.eq(sql_guild_id).and(
+					archived_partners::partnership_id
+						.eq_any(
+							archived_partner_users::table
+								.filter(archived_partner_users::user_id.eq(sql_user_id))
+								.select(archived_partner_users::partnership_id),
+						)
+						.or(archived_partners::partnership_id.eq_any(
+							archived_partner_self_users::table
+								.filter(archived_partner_self_users::user_id.eq(sql_user_id))
+								.select(archived_partner_self_users::partnership),
+						)),
+				),
+			)
+			.order(archived_partners::display_name.asc())
+			.select(archived_partners::display_name)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let requested_names: Vec<String> = partnership_requests::table
+			.filter(
+				partnership_requests::requesting_guild
+					.eq(sql_guild_id)
+					.and(partnership_requests::requester.eq(sql_user_id)),
+			)
+			.order(partnership_requests::requested_guild_name.asc())
+			.select(partnership_requests::requested_guild_name)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let changed_names: Vec<String> = partners::table
+			.filter(
+				partners::guild.eq(sql_guild_id).and(
+					partners::partnership_id.eq_any(
+						pending_partner_changes::table
+							.filter(pending_partner_changes::requested_by.eq(sql_user_id))
+							.select(pending_partner_changes::partnership_id),
+					),
+				),
+			)
+			.order(partners::display_name.asc())
+			.select(partners::display_name)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let exempt_count: i64 = partner_role_exempt_users::table
+			.filter(
+				partner_role_exempt_users::guild_id
+					.eq(sql_guild_id)
+					.and(partner_role_exempt_users::user_id.eq(sql_user_id)),
+			)
+			.count()
+			.get_result(&mut *db_connection)
+			.into_diagnostic()?;
+
+		(
+			partner_role,
+			partner_names,
+			partner_self_names,
+			archived_names,
+			requested_names,
+			changed_names,
+			exempt_count > 0,
+		)
+	};
+
+	let mut message_lines: Vec<String> = vec![format!("Data stored for <@{}> in this server:", user.id.get())];
+	if !partner_names.is_empty() {
+		message_lines.push(String::from("**Represents these partners:**"));
+		for name in partner_names.iter() {
+			message_lines.push(format!("- {}", name));
+		}
+	}
+	if !partner_self_names.is_empty() {
+		message_lines.push(String::from("**Represents us to these partners:**"));
+		for name in partner_self_names.iter() {
+			message_lines.push(format!("- {}", name));
+		}
+	}
+	if !archived_names.is_empty() {
+		message_lines.push(String::from("**Listed with these removed partners:**"));
+		for name in archived_names.iter() {
+			message_lines.push(format!("- {}", name));
+		}
+	}
+	if !requested_names.is_empty() {
+		message_lines.push(String::from("**Requested partnerships with these servers:**"));
+		for name in requested_names.iter() {
+			message_lines.push(format!("- {}", name));
+		}
+	}
+	if !changed_names.is_empty() {
+		message_lines.push(String::from("**Has changes waiting for approval for these partners:**"));
+		for name in changed_names.iter() {
+			message_lines.push(format!("- {}", name));
+		}
+	}
+	if is_role_exempt {
+		message_lines.push(String::from("**Exempt from partner role management**"));
+	}
+	// Staff log entries live in the log channel rather than the database, so there's nothing else to list
+	if partner_names.is_empty()
+		&& partner_self_names.is_empty()
+		&& archived_names.is_empty()
+		&& requested_names.is_empty()
+		&& changed_names.is_empty()
+		&& !is_role_exempt
+	{
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(format!("No data is stored for <@{}> in this server.", user.id.get()));
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let erase_button_id = cuid2::create_id();
+	let cancel_button_id = cuid2::create_id();
+	let erase_button = CreateButton::new(&erase_button_id)
+		.label("Erase")
+		.style(ButtonStyle::Danger);
+	let cancel_button = CreateButton::new(&cancel_button_id)
+		.label("Cancel")
+		.style(ButtonStyle::Secondary);
+	let buttons_row = CreateActionRow::Buttons(vec![erase_button, cancel_button]);
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(message_lines.join("\n"))
+		.components(vec![buttons_row]);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	let Some(interaction) = ComponentInteractionCollector::new(&ctx.shard)
+		.custom_ids(vec![erase_button_id.clone(), cancel_button_id.clone()])
+		.timeout(Duration::from_secs(120))
+		.await
+	else {
+		let message = EditInteractionResponse::new().components(Vec::new());
+		command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+		return Ok(());
+	};
+
+	if interaction.data.custom_id == cancel_button_id {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("No data was erased.");
+		interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		let message = EditInteractionResponse::new().components(Vec::new());
+		command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+		return Ok(());
+	}
+
+	{
+		let mut db_connection = db_connection.lock().await;
+		db_connection
+			.transaction(|db_connection| {
+				let guild_partnerships = partners::table
+					.filter(partners::guild.eq(sql_guild_id))
+					.select(partners::partnership_id);
+				diesel::delete(partner_users::table)
+					.filter(
+						partner_users::user_id
+							.eq(sql_user_id)
+							.and(partner_users::partnership_id.eq_any(guild_partnerships)),
+					)
+					.execute(db_connection)?;
+				diesel::delete(partner_self_users::table)
+					.filter(
+						partner_self_users::user_id
+							.eq(sql_user_id)
+							.and(partner_self_users::partnership.eq_any(guild_partnerships)),
+					)
+					.execute(db_connection)?;
+				let guild_archived_partnerships = archived_partners::table
+					.filter(archived_partners::guild.eq(sql_guild_id))
+					.select(archived_partners::partnership_id);
+				diesel::delete(archived_partner_users::table)
+					.filter(
+						archived_partner_users::user_id
+							.eq(sql_user_id)
+							.and(archived_partner_users::partnership_id.eq_any(guild_archived_partnerships)),
+					)
+					.execute(db_connection)?;
+				diesel::delete(archived_partner_self_users::table)
+					.filter(
+						archived_partner_self_users::user_id
+							.eq(sql_user_id)
+							.and(archived_partner_self_users::partnership.eq_any(guild_archived_partnerships)),
+					)
+					.execute(db_connection)?;
+				diesel::delete(pending_partner_changes::table)
+					.filter(
+						pending_partner_changes::requested_by
+							.eq(sql_user_id)
+							.and(pending_partner_changes::partnership_id.eq_any(guild_partnerships)),
+					)
+					.execute(db_connection)?;
+				// Pending requests can't be answered without knowing who made them, so they're withdrawn
+				diesel::delete(partnership_requests::table)
+					.filter(
+						partnership_requests::requesting_guild
+							.eq(sql_guild_id)
+							.and(partnership_requests::requester.eq(sql_user_id)),
+					)
+					.execute(db_connection)?;
+				diesel::delete(partner_role_exempt_users::table)
+					.filter(
+						partner_role_exempt_users::guild_id
+							.eq(sql_guild_id)
+							.and(partner_role_exempt_users::user_id.eq(sql_user_id)),
+					)
+					.execute(db_connection)?;
+				QueryResult::Ok(())
+			})
+			.into_diagnostic()?;
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.content(format!("Erased all data stored for <@{}>.", user.id.get()))
+		.allowed_mentions(CreateAllowedMentions::new());
+	interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+	let message = EditInteractionResponse::new().components(Vec::new());
+	command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+
+	let embed = log_embed(LogKind::Removed, "User data erased", Some(command.user.id))
+		.field("User", format!("<@{}>", user.id.get()), true)
+		.field("Representative for", partner_names.len().to_string(), true)
+		.field("Our representative for", partner_self_names.len().to_string(), true);
+	send_staff_log(ctx, guild, embed).await?;
+
+	if let Some(partner_role) = partner_role {
+		sync_role_for_guild(ctx, guild, partner_role).await?;
+	}
+
+	Ok(())
+}
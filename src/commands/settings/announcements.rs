@@ -0,0 +1,245 @@
+use crate::database::get_database_connection;
+use crate::models::GuildSettings;
+use crate::schema::guild_settings;
+use crate::sync::announcement::{ADD_ANNOUNCEMENT_PLACEHOLDERS, REMOVE_ANNOUNCEMENT_PLACEHOLDERS};
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, InputTextStyle, ResolvedOption, ResolvedValue};
+use serenity::utils::CreateQuickModal;
+use std::time::Duration;
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(option) = options.first() else {
+		bail!("Insufficient subcommands passed to settings announcements command");
+	};
+	let ResolvedValue::SubCommand(subcommand_options) = &option.value else {
+		bail!("Incorrect data type passed for settings announcements subcommand");
+	};
+	match option.name {
+		"edit_templates" => edit_templates(ctx, command).await,
+		"get" => get(ctx, command).await,
+		"set_channel" => set_channel(ctx, command, subcommand_options).await,
+		_ => bail!(
+			"Unexpected subcommand passed to settings announcements: {}",
+			option.name
+		),
+	}
+}
+
+async fn get(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+
+	let guild_settings: Option<GuildSettings> = guild_settings::table
+		.find(sql_guild_id)
+		.first(&mut *db_connection)
+		.optional()
+		.into_diagnostic()?;
+
+	let reply = match guild_settings {
+		Some(settings) => {
+			let mut reply_lines = match settings.announcement_channel {
+				Some(channel) => vec![format!("New partners are announced in <#{}>.", channel as u64)],
+				None => vec![String::from("Partner announcements are disabled.")],
+			};
+			reply_lines.push(format!(
+				"New partner announcement:\n> {}",
+				settings.add_announcement_template.replace('\n', "\n> ")
+			));
+			if settings.remove_announcement_template.is_empty() {
+				reply_lines.push(String::from("Removed partners are not announced."));
+			} else {
+				reply_lines.push(format!(
+					"Removed partner announcement:\n> {}",
+					settings.remove_announcement_template.replace('\n', "\n> ")
+				));
+			}
+			reply_lines.join("\n")
+		}
+		None => GUILD_NOT_SET_UP.to_string(),
+	};
+
+	let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reply);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn set_channel(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let announcement_channel = match options.first() {
+		Some(channel_option) => {
+			ensure!(
+				channel_option.name == "announcement_channel",
+				severity = Severity::Error,
+				"wrong option received by settings announcements set_channel command"
+			);
+			let ResolvedValue::Channel(channel) = channel_option.value else {
+				bail!("Channel option got a non-channel value: {:?}", channel_option);
+			};
+			let channel = channel.id.to_channel(&ctx.http).await.into_diagnostic()?;
+			let Some(channel) = channel.guild() else {
+				bail!("non-guild channel passed as the announcement channel");
+			};
+			Some(channel)
+		}
+		None => None,
+	};
+
+	if let Some(channel) = &announcement_channel {
+		if channel.guild_id != guild {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("The provided channel isn't in this server.");
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let sql_channel_id = announcement_channel.as_ref().map(|channel| channel.id.get() as i64);
+
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+
+	let updated_count = diesel::update(guild_settings::table)
+		.filter(guild_settings::guild_id.eq(sql_guild_id))
+		.set(guild_settings::announcement_channel.eq(sql_channel_id))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+
+	let message = if updated_count == 0 {
+		CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP)
+	} else {
+		match announcement_channel {
+			Some(channel) => CreateInteractionResponseMessage::new().content(format!(
+				"Partner announcements will now be posted to <#{}>.",
+				channel.id.get()
+			)),
+			None => CreateInteractionResponseMessage::new().content("Disabled partner announcements."),
+		}
+	};
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn edit_templates(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+
+	let guild_settings: Option<GuildSettings> = {
+		let mut db_connection = db_connection.lock().await;
+		guild_settings::table
+			.find(sql_guild_id)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?
+	};
+	let Some(guild_settings) = guild_settings else {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP);
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	};
+
+	let add_template_input = CreateInputText::new(InputTextStyle::Paragraph, "New Partner Announcement", "")
+		.placeholder(format!("Available placeholders: {}", ADD_ANNOUNCEMENT_PLACEHOLDERS))
+		.max_length(2000)
+		.required(true)
+		.value(&guild_settings.add_announcement_template);
+	let remove_template_input = CreateInputText::new(InputTextStyle::Paragraph, "Removed Partner Announcement", "")
+		.placeholder(format!(
+			"Available placeholders: {}; leave blank to not announce removals",
+			REMOVE_ANNOUNCEMENT_PLACEHOLDERS
+		))
+		.max_length(2000)
+		.required(false)
+		.value(&guild_settings.remove_announcement_template);
+
+	let modal = CreateQuickModal::new("Partner Announcements")
+		.timeout(Duration::from_secs(600))
+		.field(add_template_input)
+		.field(remove_template_input);
+	let modal_response = command.quick_modal(ctx, modal).await.into_diagnostic()?;
+
+	let Some(modal_response) = modal_response else {
+		return Ok(());
+	};
+	let mut inputs_iter = modal_response.inputs.into_iter();
+	let Some(add_template) = inputs_iter.next() else {
+		bail!("Required new partner announcement template was not entered");
+	};
+	let remove_template = inputs_iter.next().unwrap_or_default();
+
+	if add_template.contains("{reps}") {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("The new partner announcement can't use `{reps}`, since new partners don't have representatives yet. The templates were not updated.");
+		modal_response
+			.interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	{
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(guild_settings::table)
+			.filter(guild_settings::guild_id.eq(sql_guild_id))
+			.set((
+				guild_settings::add_announcement_template.eq(&add_template),
+				guild_settings::remove_announcement_template.eq(&remove_template),
+			))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?;
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content("Updated the partner announcement templates.");
+	modal_response
+		.interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
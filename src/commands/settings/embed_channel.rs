@@ -1,16 +1,13 @@
 use crate::database::get_database_connection;
 use crate::models::GuildSettings;
-use crate::schema::{guild_settings, published_messages};
-use crate::sync::embed::{remove_embed, update_embed};
+use crate::schema::guild_settings;
+use crate::sync::embed_queue::{move_default_publish_channel, queue_embed_update};
 use crate::utils::setup_check::GUILD_NOT_SET_UP;
 use diesel::prelude::*;
 use miette::{bail, ensure, IntoDiagnostic, Severity};
-use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse};
 use serenity::client::Context;
-use serenity::http::{ErrorResponse, HttpError, StatusCode};
 use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
-use serenity::model::id::ChannelId;
-use serenity::prelude::SerenityError;
 
 pub async fn execute(
 	ctx: &Context,
@@ -27,6 +24,7 @@ pub async fn execute(
 	match option.name {
 		"get" => get(ctx, command).await,
 		"set" => set(ctx, command, subcommand_options).await,
+		"set_crosspost" => set_crosspost(ctx, command, subcommand_options).await,
 		_ => bail!("Invalid subcommand passed to settings embed_channel command"),
 	}
 }
@@ -40,17 +38,27 @@ async fn get(ctx: &Context, command: &CommandInteraction) -> miette::Result<()>
 	let db_connection = get_database_connection(ctx).await;
 	let mut db_connection = db_connection.lock().await;
 
-	let embed_channel_id: Option<i64> = guild_settings::table
+	let embed_channel_settings: Option<(i64, bool)> = guild_settings::table
 		.find(sql_guild_id)
-		.select(guild_settings::publish_channel)
+		.select((guild_settings::publish_channel, guild_settings::crosspost_announcements))
 		.first(&mut *db_connection)
 		.optional()
 		.into_diagnostic()?;
 
-	let message = match embed_channel_id {
-		Some(id) => CreateInteractionResponseMessage::new()
-			.content(format!("The partnership embed is published to <#{}>.", id))
-			.ephemeral(true),
+	let message = match embed_channel_settings {
+		Some((id, crosspost)) => {
+			let crosspost_note = if crosspost {
+				"New messages in announcement channels are crossposted."
+			} else {
+				"New messages in announcement channels aren't crossposted."
+			};
+			CreateInteractionResponseMessage::new()
+				.content(format!(
+					"The partnership embed is published to <#{}>. {}",
+					id, crosspost_note
+				))
+				.ephemeral(true)
+		}
 		None => CreateInteractionResponseMessage::new()
 			.content(GUILD_NOT_SET_UP)
 			.ephemeral(true),
@@ -97,91 +105,106 @@ async fn set(ctx: &Context, command: &CommandInteraction, options: &[ResolvedOpt
 
 	let sql_guild_id = guild.get() as i64;
 	let db_connection = get_database_connection(ctx).await;
-	let (current_channel_id, message_delete_errors) = {
+	let guild_settings: Option<GuildSettings> = {
 		let mut db_connection = db_connection.lock().await;
-
-		let guild_settings: Option<GuildSettings> = guild_settings::table
+		guild_settings::table
 			.find(sql_guild_id)
 			.first(&mut *db_connection)
 			.optional()
+			.into_diagnostic()?
+	};
+	let Some(guild_settings) = guild_settings else {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP);
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
 			.into_diagnostic()?;
-		let Some(guild_settings) = guild_settings else {
-			let message = CreateInteractionResponseMessage::new()
-				.ephemeral(true)
-				.content(GUILD_NOT_SET_UP);
-			command
-				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
-				.await
-				.into_diagnostic()?;
-			return Ok(());
-		};
-		let current_channel_id = guild_settings.publish_channel;
-		let current_messages: Vec<i64> = published_messages::table
-			.filter(published_messages::guild_id.eq(sql_guild_id))
-			.select(published_messages::message_id)
-			.load(&mut *db_connection)
-			.into_diagnostic()?;
-		let current_channel_id = current_channel_id as u64;
-		let current_messages: Vec<u64> = current_messages
-			.into_iter()
-			.map(|message_id| message_id as u64)
-			.collect();
-		let current_channel = ChannelId::new(current_channel_id);
-
-		let mut message_delete_errors: Vec<SerenityError> = Vec::new();
-		for message_id in current_messages {
-			if let Err(error) = current_channel.delete_message(&ctx.http, message_id).await {
-				// If the message was already deleted, that's not an error
-				if let SerenityError::Http(HttpError::UnsuccessfulRequest(ErrorResponse {
-					status_code: StatusCode::NOT_FOUND,
-					..
-				})) = error
-				{
-					continue;
-				}
-				message_delete_errors.push(error);
-			}
-		}
-		diesel::delete(published_messages::table)
-			.filter(published_messages::guild_id.eq(sql_guild_id))
-			.execute(&mut *db_connection)
-			.into_diagnostic()?;
-
-		(current_channel_id, message_delete_errors)
+		return Ok(());
 	};
+	let current_channel_id = guild_settings.publish_channel as u64;
+
+	// Deleting the old messages can take a while
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Defer(Default::default()))
+		.await
+		.into_diagnostic()?;
 
+	// The move goes through the update worker so it can't overlap with a publish to the same messages
+	let message_delete_errors = move_default_publish_channel(ctx, guild, embed_channel.id).await?;
 	if !message_delete_errors.is_empty() {
 		let mut message_lines = vec![String::from("Updating the publish channel failed; the bot was unable to delete the message from the old channel. You will need to delete the messages manually.")];
 		for error in message_delete_errors {
 			message_lines.push(format!("- {}", error));
 		}
-		let message = CreateInteractionResponseMessage::new().content(message_lines.join("\n"));
-		command
-			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
-			.await
-			.into_diagnostic()?;
+		let message = EditInteractionResponse::new().content(message_lines.join("\n"));
+		command.edit_response(&ctx.http, message).await.into_diagnostic()?;
 		return Ok(());
 	}
 
-	remove_embed(ctx, guild).await?;
+	queue_embed_update(ctx, guild).await;
+
+	let mut message_content = format!(
+		"Updated embed channel from <#{}>, to <#{}>.",
+		current_channel_id,
+		embed_channel.id.get()
+	);
+	if guild_settings.draft_mode {
+		message_content.push_str(
+			" Draft mode is on, so the partner list will be posted in the new channel when changes are published.",
+		);
+	}
+	let message = EditInteractionResponse::new().content(message_content);
+	command.edit_response(&ctx.http, message).await.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn set_crosspost(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let Some(enabled_option) = options.first() else {
+		bail!("Missing option for settings embed_channel set_crosspost command");
+	};
+	ensure!(
+		enabled_option.name == "enabled",
+		severity = Severity::Error,
+		"wrong option received by settings embed_channel set_crosspost command"
+	);
+	let ResolvedValue::Boolean(enabled) = enabled_option.value else {
+		bail!("Enabled option got a non-boolean value: {:?}", enabled_option);
+	};
 
-	{
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let updated_count = {
 		let mut db_connection = db_connection.lock().await;
-		let sql_channel_id = embed_channel.id.get() as i64;
 		diesel::update(guild_settings::table)
 			.filter(guild_settings::guild_id.eq(sql_guild_id))
-			.set(guild_settings::publish_channel.eq(sql_channel_id))
+			.set(guild_settings::crosspost_announcements.eq(enabled))
 			.execute(&mut *db_connection)
-			.into_diagnostic()?;
-	}
-
-	update_embed(ctx, guild).await?;
+			.into_diagnostic()?
+	};
 
-	let message = CreateInteractionResponseMessage::new().content(format!(
-		"Updated embed channel from <#{}>, to <#{}>.",
-		current_channel_id,
-		embed_channel.id.get()
-	));
+	let message = if updated_count == 0 {
+		CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP)
+	} else if enabled {
+		CreateInteractionResponseMessage::new().content(
+			"New embed messages in announcement channels will be crossposted to servers following the channel.",
+		)
+	} else {
+		CreateInteractionResponseMessage::new()
+			.content("New embed messages in announcement channels will no longer be crossposted.")
+	};
 	command
 		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
 		.await
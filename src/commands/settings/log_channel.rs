@@ -0,0 +1,126 @@
+use crate::database::get_database_connection;
+use crate::schema::guild_settings;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(option) = options.first() else {
+		bail!("Insufficient subcommands passed to settings log_channel command");
+	};
+	let ResolvedValue::SubCommand(subcommand_options) = &option.value else {
+		bail!("Incorrect data type passed for settings log_channel subcommand");
+	};
+	match option.name {
+		"get" => get(ctx, command).await,
+		"set" => set(ctx, command, subcommand_options).await,
+		_ => bail!("Unexpected subcommand passed to settings log_channel: {}", option.name),
+	}
+}
+
+async fn get(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+
+	let log_channel: Option<Option<i64>> = guild_settings::table
+		.find(sql_guild_id)
+		.select(guild_settings::log_channel)
+		.first(&mut *db_connection)
+		.optional()
+		.into_diagnostic()?;
+
+	let reply = match log_channel {
+		Some(Some(id)) => format!("Partnership events are logged to <#{}>.", id as u64),
+		Some(None) => String::from("There is no log channel."),
+		None => GUILD_NOT_SET_UP.to_string(),
+	};
+
+	let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reply);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn set(ctx: &Context, command: &CommandInteraction, options: &[ResolvedOption<'_>]) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let log_channel = match options.first() {
+		Some(channel_option) => {
+			ensure!(
+				channel_option.name == "log_channel",
+				severity = Severity::Error,
+				"wrong option received by settings log_channel set command"
+			);
+			let ResolvedValue::Channel(channel) = channel_option.value else {
+				bail!("Channel option got a non-channel value: {:?}", channel_option);
+			};
+			let channel = channel.id.to_channel(&ctx.http).await.into_diagnostic()?;
+			let Some(channel) = channel.guild() else {
+				bail!("non-guild channel passed as the log channel");
+			};
+			Some(channel)
+		}
+		None => None,
+	};
+
+	if let Some(channel) = &log_channel {
+		if channel.guild_id != guild {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("The provided channel isn't in this server.");
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let sql_channel_id = log_channel.as_ref().map(|channel| channel.id.get() as i64);
+
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+
+	let updated_count = diesel::update(guild_settings::table)
+		.filter(guild_settings::guild_id.eq(sql_guild_id))
+		.set(guild_settings::log_channel.eq(sql_channel_id))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+
+	let message = if updated_count == 0 {
+		CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP)
+	} else {
+		match log_channel {
+			Some(channel) => CreateInteractionResponseMessage::new().content(format!(
+				"Partnership events will now be logged to <#{}>.",
+				channel.id.get()
+			)),
+			None => CreateInteractionResponseMessage::new().content("Removed the log channel."),
+		}
+	};
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
@@ -5,10 +5,43 @@ use serenity::model::application::{CommandInteraction, CommandOptionType, Comman
 use serenity::model::channel::ChannelType;
 use serenity::model::permissions::Permissions;
 
+mod announcements;
+mod draft_mode;
 mod embed_channel;
+mod log_channel;
 mod partner_role;
+mod partner_role_exempt;
+mod rep_changes;
+mod republish_deleted;
+mod webhook_publishing;
 
 pub fn definition() -> CreateCommand {
+	let get_draft_mode_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"get",
+		"Get whether changes are held as drafts until published",
+	);
+	let set_draft_mode_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set",
+		"Change whether changes are held as drafts until published",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Boolean,
+			"enabled",
+			"Whether to hold changes to the partner list until they're published",
+		)
+		.required(true),
+	);
+	let draft_mode_command = CreateCommandOption::new(
+		CommandOptionType::SubCommandGroup,
+		"draft_mode",
+		"Holding partner list changes until they're explicitly published",
+	)
+	.add_sub_option(get_draft_mode_command)
+	.add_sub_option(set_draft_mode_command);
+
 	let get_embed_channel_command = CreateCommandOption::new(
 		CommandOptionType::SubCommand,
 		"get",
@@ -26,7 +59,20 @@ pub fn definition() -> CreateCommand {
 			"The channel in which to show the partnership embed",
 		)
 		.required(true)
-		.channel_types(vec![ChannelType::Text]),
+		.channel_types(vec![ChannelType::Text, ChannelType::News, ChannelType::Forum]),
+	);
+	let set_crosspost_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_crosspost",
+		"Change whether new embed messages in announcement channels are crossposted",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Boolean,
+			"enabled",
+			"Whether to crosspost new embed messages to servers following the announcement channel",
+		)
+		.required(true),
 	);
 	let embed_channel_command = CreateCommandOption::new(
 		CommandOptionType::SubCommandGroup,
@@ -34,7 +80,68 @@ pub fn definition() -> CreateCommand {
 		"The channel to which the embed is published",
 	)
 	.add_sub_option(get_embed_channel_command)
-	.add_sub_option(set_embed_channel_command);
+	.add_sub_option(set_embed_channel_command)
+	.add_sub_option(set_crosspost_command);
+
+	let get_log_channel_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"get",
+		"Get the channel to which partnership events are logged",
+	);
+	let set_log_channel_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set",
+		"Change the channel to which partnership events are logged",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Channel,
+			"log_channel",
+			"The channel in which to log partnership events; leave blank to disable logging",
+		)
+		.required(false)
+		.channel_types(vec![ChannelType::Text]),
+	);
+	let log_channel_command = CreateCommandOption::new(
+		CommandOptionType::SubCommandGroup,
+		"log_channel",
+		"The channel to which partnership events are logged for staff",
+	)
+	.add_sub_option(get_log_channel_command)
+	.add_sub_option(set_log_channel_command);
+
+	let edit_announcement_templates_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"edit_templates",
+		"Edit the messages posted when partners are added or removed",
+	);
+	let get_announcements_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"get",
+		"Get the current partner announcement settings",
+	);
+	let set_announcement_channel_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_channel",
+		"Change the channel in which partner changes are announced",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Channel,
+			"announcement_channel",
+			"The channel in which to announce partner changes; leave blank to disable announcements",
+		)
+		.required(false)
+		.channel_types(vec![ChannelType::Text, ChannelType::News]),
+	);
+	let announcements_command = CreateCommandOption::new(
+		CommandOptionType::SubCommandGroup,
+		"announcements",
+		"Public announcements of partner changes",
+	)
+	.add_sub_option(edit_announcement_templates_command)
+	.add_sub_option(get_announcements_command)
+	.add_sub_option(set_announcement_channel_command);
 
 	let get_partner_role_command = CreateCommandOption::new(
 		CommandOptionType::SubCommand,
@@ -62,13 +169,161 @@ pub fn definition() -> CreateCommand {
 	.add_sub_option(get_partner_role_command)
 	.add_sub_option(set_partner_role_command);
 
+	let add_partner_role_exempt_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"add",
+		"Exempts a user or role from partner role synchronization",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Mentionable,
+			"target",
+			"The user or role for which the bot should never add or remove the partner role",
+		)
+		.required(true),
+	);
+	let list_partner_role_exempt_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"list",
+		"Lists users and roles exempt from partner role synchronization",
+	);
+	let remove_partner_role_exempt_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"remove",
+		"Removes a partner role synchronization exemption",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Mentionable,
+			"target",
+			"The user or role to stop exempting",
+		)
+		.required(true),
+	);
+	let partner_role_exempt_command = CreateCommandOption::new(
+		CommandOptionType::SubCommandGroup,
+		"partner_role_exempt",
+		"Users and roles for which the partner role is managed manually",
+	)
+	.add_sub_option(add_partner_role_exempt_command)
+	.add_sub_option(list_partner_role_exempt_command)
+	.add_sub_option(remove_partner_role_exempt_command);
+
+	let get_rep_changes_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"get",
+		"Get whether changes from partner representatives need staff approval",
+	);
+	let set_rep_changes_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set",
+		"Change whether changes from partner representatives need staff approval",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Boolean,
+			"need_approval",
+			"Whether to hold changes representatives make with /my_partnership until staff approve them",
+		)
+		.required(true),
+	);
+	let rep_changes_command = CreateCommandOption::new(
+		CommandOptionType::SubCommandGroup,
+		"rep_changes",
+		"Partner representatives changing their own listing",
+	)
+	.add_sub_option(get_rep_changes_command)
+	.add_sub_option(set_rep_changes_command);
+
+	let get_republish_deleted_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"get",
+		"Get whether deleted partner embed messages are posted again",
+	);
+	let set_republish_deleted_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set",
+		"Change whether deleted partner embed messages are posted again",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Boolean,
+			"enabled",
+			"Whether to post partner embed messages again when they're deleted",
+		)
+		.required(true),
+	);
+	let republish_deleted_command = CreateCommandOption::new(
+		CommandOptionType::SubCommandGroup,
+		"republish_deleted",
+		"Posting partner embed messages again when they're deleted",
+	)
+	.add_sub_option(get_republish_deleted_command)
+	.add_sub_option(set_republish_deleted_command);
+
+	let get_webhook_publishing_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"get",
+		"Get how the partner list is published",
+	);
+	let set_webhook_publishing_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set",
+		"Change whether the partner list is published through a webhook",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Boolean,
+			"enabled",
+			"Whether to publish the partner list through a webhook instead of as the bot",
+		)
+		.required(true),
+	);
+	let set_webhook_identity_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"set_identity",
+		"Change the name and avatar shown on partner list webhook messages",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::String,
+			"username",
+			"The name to show on partner list messages; leave blank for the default",
+		)
+		.required(false)
+		.max_length(80),
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::String,
+			"avatar_url",
+			"A link to the avatar to show on partner list messages; leave blank for the default",
+		)
+		.required(false),
+	);
+	let webhook_publishing_command = CreateCommandOption::new(
+		CommandOptionType::SubCommandGroup,
+		"webhook_publishing",
+		"Publishing the partner list through a webhook with a custom name and avatar",
+	)
+	.add_sub_option(get_webhook_publishing_command)
+	.add_sub_option(set_webhook_publishing_command)
+	.add_sub_option(set_webhook_identity_command);
+
 	CreateCommand::new("settings")
 		.kind(CommandType::ChatInput)
 		.default_member_permissions(Permissions::MANAGE_GUILD)
 		.dm_permission(false)
 		.description("Manages settings for partner management for the server")
+		.add_option(announcements_command)
+		.add_option(draft_mode_command)
 		.add_option(embed_channel_command)
+		.add_option(log_channel_command)
 		.add_option(partner_role_command)
+		.add_option(partner_role_exempt_command)
+		.add_option(rep_changes_command)
+		.add_option(republish_deleted_command)
+		.add_option(webhook_publishing_command)
 }
 
 pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
@@ -83,8 +338,15 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		bail!("Incorrect data type for settings subcommands: {:?}", subcommand);
 	};
 	match subcommand.name {
+		"announcements" => announcements::execute(ctx, command, subcommand_options).await,
+		"draft_mode" => draft_mode::execute(ctx, command, subcommand_options).await,
 		"embed_channel" => embed_channel::execute(ctx, command, subcommand_options).await,
+		"log_channel" => log_channel::execute(ctx, command, subcommand_options).await,
 		"partner_role" => partner_role::execute(ctx, command, subcommand_options).await,
+		"partner_role_exempt" => partner_role_exempt::execute(ctx, command, subcommand_options).await,
+		"rep_changes" => rep_changes::execute(ctx, command, subcommand_options).await,
+		"republish_deleted" => republish_deleted::execute(ctx, command, subcommand_options).await,
+		"webhook_publishing" => webhook_publishing::execute(ctx, command, subcommand_options).await,
 		_ => bail!("Unexpected subcommand for settings: {}", subcommand.name),
 	}
 }
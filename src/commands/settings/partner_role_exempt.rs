@@ -0,0 +1,254 @@
+use crate::database::get_database_connection;
+use crate::models::{PartnerRoleExemptRole, PartnerRoleExemptUser};
+use crate::schema::{guild_settings, partner_role_exempt_roles, partner_role_exempt_users};
+use crate::sync::role::sync_role_for_guild;
+use crate::utils::setup_check::{guild_setup_check_with_reply, GUILD_NOT_SET_UP};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DbError};
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateAllowedMentions, CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+use serenity::model::id::RoleId;
+
+/// A user or role that can be exempted from partner role sync
+enum ExemptionTarget {
+	User(u64),
+	Role(u64),
+}
+
+impl ExemptionTarget {
+	fn mention(&self) -> String {
+		match self {
+			Self::User(id) => format!("<@{}>", id),
+			Self::Role(id) => format!("<@&{}>", id),
+		}
+	}
+}
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(option) = options.first() else {
+		bail!("Insufficient subcommands passed to settings partner_role_exempt command");
+	};
+	let ResolvedValue::SubCommand(subcommand_options) = &option.value else {
+		bail!("Incorrect data type passed for settings partner_role_exempt subcommand");
+	};
+	match option.name {
+		"add" => add(ctx, command, subcommand_options).await,
+		"list" => list(ctx, command).await,
+		"remove" => remove(ctx, command, subcommand_options).await,
+		_ => bail!(
+			"Unexpected subcommand passed to settings partner_role_exempt: {}",
+			option.name
+		),
+	}
+}
+
+fn get_target(options: &[ResolvedOption<'_>]) -> miette::Result<ExemptionTarget> {
+	let Some(target_option) = options.first() else {
+		bail!("Insufficient options passed to settings partner_role_exempt command");
+	};
+	ensure!(
+		target_option.name == "target",
+		severity = Severity::Error,
+		"wrong option received by settings partner_role_exempt command"
+	);
+	match target_option.value {
+		ResolvedValue::User(user, _) => Ok(ExemptionTarget::User(user.id.get())),
+		ResolvedValue::Role(role) => Ok(ExemptionTarget::Role(role.id.get())),
+		_ => bail!("Got a non-mentionable value for a mentionable option"),
+	}
+}
+
+async fn add(ctx: &Context, command: &CommandInteraction, options: &[ResolvedOption<'_>]) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let target = get_target(options)?;
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+	if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+		return Ok(());
+	}
+
+	let insert_result = match target {
+		ExemptionTarget::User(user_id) => diesel::insert_into(partner_role_exempt_users::table)
+			.values(PartnerRoleExemptUser {
+				guild_id: sql_guild_id,
+				user_id: user_id as i64,
+			})
+			.execute(&mut *db_connection),
+		ExemptionTarget::Role(role_id) => diesel::insert_into(partner_role_exempt_roles::table)
+			.values(PartnerRoleExemptRole {
+				guild_id: sql_guild_id,
+				role_id: role_id as i64,
+			})
+			.execute(&mut *db_connection),
+	};
+
+	let message = match insert_result {
+		Ok(_) => CreateInteractionResponseMessage::new().content(format!(
+			"{} is now exempt from partner role synchronization.",
+			target.mention()
+		)),
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+			CreateInteractionResponseMessage::new().ephemeral(true).content(format!(
+				"{} is already exempt from partner role synchronization.",
+				target.mention()
+			))
+		}
+		Err(error) => bail!(error),
+	};
+	let message = message.allowed_mentions(CreateAllowedMentions::new());
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn list(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+	if !guild_setup_check_with_reply(ctx, command, guild, &mut db_connection).await? {
+		return Ok(());
+	}
+
+	let exempt_users: Vec<i64> = partner_role_exempt_users::table
+		.filter(partner_role_exempt_users::guild_id.eq(sql_guild_id))
+		.select(partner_role_exempt_users::user_id)
+		.load(&mut *db_connection)
+		.into_diagnostic()?;
+	let exempt_roles: Vec<i64> = partner_role_exempt_roles::table
+		.filter(partner_role_exempt_roles::guild_id.eq(sql_guild_id))
+		.select(partner_role_exempt_roles::role_id)
+		.load(&mut *db_connection)
+		.into_diagnostic()?;
+
+	let mut message_lines: Vec<String> = Vec::new();
+	if !exempt_users.is_empty() {
+		message_lines.push(String::from(
+			"The following users are exempt from partner role synchronization:",
+		));
+		for user_id in exempt_users {
+			message_lines.push(format!("- <@{}>", user_id as u64));
+		}
+	}
+	if !exempt_roles.is_empty() {
+		message_lines.push(String::from(
+			"Members with the following roles are exempt from partner role synchronization:",
+		));
+		for role_id in exempt_roles {
+			message_lines.push(format!("- <@&{}>", role_id as u64));
+		}
+	}
+	if message_lines.is_empty() {
+		message_lines.push(String::from("Nobody is exempt from partner role synchronization."));
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.ephemeral(true)
+		.content(message_lines.join("\n"))
+		.allowed_mentions(CreateAllowedMentions::new());
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn remove(ctx: &Context, command: &CommandInteraction, options: &[ResolvedOption<'_>]) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let target = get_target(options)?;
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (removed_count, partner_role) = {
+		let mut db_connection = db_connection.lock().await;
+
+		let partner_role: Option<Option<i64>> = guild_settings::table
+			.find(sql_guild_id)
+			.select(guild_settings::partner_role)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let Some(partner_role) = partner_role else {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(GUILD_NOT_SET_UP);
+			command
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		};
+
+		let removed_count = match target {
+			ExemptionTarget::User(user_id) => diesel::delete(partner_role_exempt_users::table)
+				.filter(
+					partner_role_exempt_users::guild_id
+						.eq(sql_guild_id)
+						.and(partner_role_exempt_users::user_id.eq(user_id as i64)),
+				)
+				.execute(&mut *db_connection)
+				.into_diagnostic()?,
+			ExemptionTarget::Role(role_id) => diesel::delete(partner_role_exempt_roles::table)
+				.filter(
+					partner_role_exempt_roles::guild_id
+						.eq(sql_guild_id)
+						.and(partner_role_exempt_roles::role_id.eq(role_id as i64)),
+				)
+				.execute(&mut *db_connection)
+				.into_diagnostic()?,
+		};
+
+		(removed_count, partner_role.map(|id| RoleId::new(id as u64)))
+	};
+
+	if removed_count == 0 {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(format!(
+				"{} is not exempt from partner role synchronization.",
+				target.mention()
+			))
+			.allowed_mentions(CreateAllowedMentions::new());
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	if let Some(partner_role) = partner_role {
+		sync_role_for_guild(ctx, guild, partner_role).await?;
+	}
+
+	let message = CreateInteractionResponseMessage::new()
+		.content(format!(
+			"{} is no longer exempt from partner role synchronization.",
+			target.mention()
+		))
+		.allowed_mentions(CreateAllowedMentions::new());
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
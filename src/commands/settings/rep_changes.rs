@@ -0,0 +1,111 @@
+use crate::database::get_database_connection;
+use crate::schema::guild_settings;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(option) = options.first() else {
+		bail!("Insufficient subcommands passed to settings rep_changes command");
+	};
+	let ResolvedValue::SubCommand(subcommand_options) = &option.value else {
+		bail!("Incorrect data type passed for settings rep_changes subcommand");
+	};
+	match option.name {
+		"get" => get(ctx, command).await,
+		"set" => set(ctx, command, subcommand_options).await,
+		_ => bail!("Unexpected subcommand passed to settings rep_changes: {}", option.name),
+	}
+}
+
+async fn get(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+
+	let need_approval: Option<bool> = guild_settings::table
+		.find(sql_guild_id)
+		.select(guild_settings::rep_changes_need_approval)
+		.first(&mut *db_connection)
+		.optional()
+		.into_diagnostic()?;
+
+	let reply = match need_approval {
+		Some(true) => String::from(
+			"Changes partner representatives make with `/my_partnership` are held until staff approve them.",
+		),
+		Some(false) => {
+			String::from("Changes partner representatives make with `/my_partnership` are applied immediately.")
+		}
+		None => GUILD_NOT_SET_UP.to_string(),
+	};
+
+	let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reply);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn set(ctx: &Context, command: &CommandInteraction, options: &[ResolvedOption<'_>]) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let Some(need_approval_option) = options.first() else {
+		bail!("Missing option for settings rep_changes set command");
+	};
+	ensure!(
+		need_approval_option.name == "need_approval",
+		severity = Severity::Error,
+		"wrong option received by settings rep_changes set command"
+	);
+	let ResolvedValue::Boolean(need_approval) = need_approval_option.value else {
+		bail!(
+			"Need approval option got a non-boolean value: {:?}",
+			need_approval_option
+		);
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let updated_count = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(guild_settings::table)
+			.filter(guild_settings::guild_id.eq(sql_guild_id))
+			.set(guild_settings::rep_changes_need_approval.eq(need_approval))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	// Changes already waiting for approval stay in the staff log to be approved or rejected either way
+	let message = if updated_count == 0 {
+		CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP)
+	} else if need_approval {
+		CreateInteractionResponseMessage::new()
+			.content("Changes from partner representatives will now be held until staff approve them.")
+	} else {
+		CreateInteractionResponseMessage::new()
+			.content("Changes from partner representatives will now be applied immediately.")
+	};
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
@@ -0,0 +1,107 @@
+use crate::database::get_database_connection;
+use crate::schema::guild_settings;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(option) = options.first() else {
+		bail!("Insufficient subcommands passed to settings republish_deleted command");
+	};
+	let ResolvedValue::SubCommand(subcommand_options) = &option.value else {
+		bail!("Incorrect data type passed for settings republish_deleted subcommand");
+	};
+	match option.name {
+		"get" => get(ctx, command).await,
+		"set" => set(ctx, command, subcommand_options).await,
+		_ => bail!(
+			"Unexpected subcommand passed to settings republish_deleted: {}",
+			option.name
+		),
+	}
+}
+
+async fn get(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+
+	let republish_deleted: Option<bool> = guild_settings::table
+		.find(sql_guild_id)
+		.select(guild_settings::republish_deleted_messages)
+		.first(&mut *db_connection)
+		.optional()
+		.into_diagnostic()?;
+
+	let reply = match republish_deleted {
+		Some(true) => String::from("Partner embed messages are posted again when they're deleted."),
+		Some(false) => String::from("Partner embed messages stay gone when they're deleted."),
+		None => GUILD_NOT_SET_UP.to_string(),
+	};
+
+	let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reply);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn set(ctx: &Context, command: &CommandInteraction, options: &[ResolvedOption<'_>]) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let Some(enabled_option) = options.first() else {
+		bail!("Missing option for settings republish_deleted set command");
+	};
+	ensure!(
+		enabled_option.name == "enabled",
+		severity = Severity::Error,
+		"wrong option received by settings republish_deleted set command"
+	);
+	let ResolvedValue::Boolean(enabled) = enabled_option.value else {
+		bail!("Enabled option got a non-boolean value: {:?}", enabled_option);
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let updated_count = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(guild_settings::table)
+			.filter(guild_settings::guild_id.eq(sql_guild_id))
+			.set(guild_settings::republish_deleted_messages.eq(enabled))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	let message = if updated_count == 0 {
+		CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP)
+	} else if enabled {
+		CreateInteractionResponseMessage::new()
+			.content("Partner embed messages will now be posted again when they're deleted.")
+	} else {
+		CreateInteractionResponseMessage::new().content(
+			"Partner embed messages will now stay gone when they're deleted. They'll be posted again the next time the partner list is published.",
+		)
+	};
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
@@ -0,0 +1,211 @@
+use crate::database::get_database_connection;
+use crate::models::GuildSettings;
+use crate::schema::guild_settings;
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use diesel::prelude::*;
+use miette::{bail, ensure, IntoDiagnostic, Severity};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(option) = options.first() else {
+		bail!("Insufficient subcommands passed to settings webhook_publishing command");
+	};
+	let ResolvedValue::SubCommand(subcommand_options) = &option.value else {
+		bail!("Incorrect data type passed for settings webhook_publishing subcommand");
+	};
+	match option.name {
+		"get" => get(ctx, command).await,
+		"set" => set(ctx, command, subcommand_options).await,
+		"set_identity" => set_identity(ctx, command, subcommand_options).await,
+		_ => bail!(
+			"Unexpected subcommand passed to settings webhook_publishing: {}",
+			option.name
+		),
+	}
+}
+
+async fn get(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+
+	let settings: Option<GuildSettings> = guild_settings::table
+		.find(sql_guild_id)
+		.first(&mut *db_connection)
+		.optional()
+		.into_diagnostic()?;
+
+	let reply = match settings {
+		Some(settings) => {
+			let mode = if settings.webhook_publishing {
+				"The partner list is published through a webhook."
+			} else {
+				"The partner list is published by the bot."
+			};
+			let username = if settings.webhook_username.is_empty() {
+				String::from("the webhook's default name")
+			} else {
+				settings.webhook_username
+			};
+			let avatar = if settings.webhook_avatar_url.is_empty() {
+				String::from("the webhook's default avatar")
+			} else {
+				settings.webhook_avatar_url
+			};
+			format!("{}\nWebhook messages use {} and {}.", mode, username, avatar)
+		}
+		None => GUILD_NOT_SET_UP.to_string(),
+	};
+
+	let message = CreateInteractionResponseMessage::new().ephemeral(true).content(reply);
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+async fn set(ctx: &Context, command: &CommandInteraction, options: &[ResolvedOption<'_>]) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let Some(enabled_option) = options.first() else {
+		bail!("Missing option for settings webhook_publishing set command");
+	};
+	ensure!(
+		enabled_option.name == "enabled",
+		severity = Severity::Error,
+		"wrong option received by settings webhook_publishing set command"
+	);
+	let ResolvedValue::Boolean(enabled) = enabled_option.value else {
+		bail!("Enabled option got a non-boolean value: {:?}", enabled_option);
+	};
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let updated_count = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(guild_settings::table)
+			.filter(guild_settings::guild_id.eq(sql_guild_id))
+			.set(guild_settings::webhook_publishing.eq(enabled))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	let message = if updated_count == 0 {
+		CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP)
+	} else if enabled {
+		CreateInteractionResponseMessage::new().content(
+			"The partner list will now be published through a webhook. The bot needs permission to manage webhooks in the embed channels.",
+		)
+	} else {
+		CreateInteractionResponseMessage::new().content("The partner list will now be published by the bot.")
+	};
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	if updated_count > 0 {
+		queue_embed_update(ctx, guild).await;
+	}
+
+	Ok(())
+}
+
+async fn set_identity(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Settings command was used outside of a guild");
+	};
+
+	let mut username = String::new();
+	let mut avatar_url = String::new();
+	for option in options.iter() {
+		let ResolvedValue::String(value) = option.value else {
+			bail!("Non-string value passed to a string option");
+		};
+		match option.name {
+			"username" => username = value.trim().to_string(),
+			"avatar_url" => avatar_url = value.trim().to_string(),
+			_ => bail!(
+				"Invalid option passed to settings webhook_publishing set_identity command: {}",
+				option.name
+			),
+		}
+	}
+
+	// Discord rejects webhook names containing these
+	let lowercase_username = username.to_lowercase();
+	if lowercase_username.contains("discord") || lowercase_username.contains("clyde") {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("Discord doesn't allow webhook names containing \"discord\" or \"clyde\".");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+	if !avatar_url.is_empty() && !avatar_url.starts_with("https://") && !avatar_url.starts_with("http://") {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("The avatar must be a link to an image.");
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let updated_count = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(guild_settings::table)
+			.filter(guild_settings::guild_id.eq(sql_guild_id))
+			.set((
+				guild_settings::webhook_username.eq(&username),
+				guild_settings::webhook_avatar_url.eq(&avatar_url),
+			))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	let message = if updated_count == 0 {
+		CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP)
+	} else {
+		CreateInteractionResponseMessage::new().content(
+			"Updated the name and avatar for webhook messages. Messages already published will be sent again with them.",
+		)
+	};
+	command
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	if updated_count > 0 {
+		queue_embed_update(ctx, guild).await;
+	}
+
+	Ok(())
+}
@@ -0,0 +1,70 @@
+use miette::{bail, ensure, Severity};
+use serenity::builder::{CreateCommand, CreateCommandOption};
+use serenity::client::Context;
+use serenity::model::application::{CommandInteraction, CommandOptionType, CommandType, ResolvedValue};
+use serenity::model::channel::ChannelType;
+use serenity::model::permissions::Permissions;
+
+mod reset;
+mod start;
+
+pub fn definition() -> CreateCommand {
+	let start_command =
+		CreateCommandOption::new(CommandOptionType::SubCommand, "start", "Set up the bot for this server")
+			.add_sub_option(
+				CreateCommandOption::new(
+					CommandOptionType::Channel,
+					"embed_channel",
+					"The channel in which to show the partnership embed",
+				)
+				.required(true)
+				.channel_types(vec![ChannelType::Text, ChannelType::News, ChannelType::Forum]),
+			);
+	let reset_command = CreateCommandOption::new(
+		CommandOptionType::SubCommand,
+		"reset",
+		"Remove the partner list and delete all of this server's partner data",
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Boolean,
+			"remove_partner_role",
+			"Whether to remove the partner role from everyone who has it",
+		)
+		.required(false),
+	)
+	.add_sub_option(
+		CreateCommandOption::new(
+			CommandOptionType::Boolean,
+			"export",
+			"Whether to attach an export of the server's partner data before it's deleted",
+		)
+		.required(false),
+	);
+
+	CreateCommand::new("setup")
+		.kind(CommandType::ChatInput)
+		.default_member_permissions(Permissions::MANAGE_GUILD)
+		.dm_permission(false)
+		.description("Set up or reset the bot for a particular guild")
+		.add_option(reset_command)
+		.add_option(start_command)
+}
+
+pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+	let options = command.data.options();
+	ensure!(
+		!options.is_empty(),
+		severity = Severity::Error,
+		"called the setup command without subcommands"
+	);
+	let subcommand = options.first().unwrap();
+	let ResolvedValue::SubCommand(subcommand_options) = &subcommand.value else {
+		bail!("Incorrect data type passed for setup subcommand option");
+	};
+	match subcommand.name {
+		"reset" => reset::execute(ctx, command, subcommand_options).await,
+		"start" => start::execute(ctx, command, subcommand_options).await,
+		_ => bail!("Unexpected subcommand for setup command: {:?}", subcommand),
+	}
+}
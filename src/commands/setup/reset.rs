@@ -0,0 +1,372 @@
+use crate::database::get_database_connection;
+use crate::models::{
+	ArchivedPartner, ArchivedPartnerSelfUser, ArchivedPartnerUser, EmbedData, GuildSettings, Partner, PartnerCategory,
+	PartnerSelfUser, PartnerUser,
+};
+use crate::schema::{
+	archived_partner_self_users, archived_partner_users, archived_partners, embed_data, guild_settings,
+	partner_categories, partner_role_exempt_roles, partner_role_exempt_users, partner_self_users, partner_users,
+	partners,
+};
+use crate::sync::embed::delete_publish_webhooks;
+use crate::sync::embed_queue::{purge_guild_data, remove_published_embeds};
+use crate::utils::setup_check::GUILD_NOT_SET_UP;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use miette::{bail, IntoDiagnostic};
+use serde_json::{json, Value};
+use serenity::builder::{
+	CreateAttachment, CreateInputText, CreateInteractionResponse, CreateInteractionResponseMessage,
+	EditInteractionResponse,
+};
+use serenity::client::Context;
+use serenity::futures::StreamExt;
+use serenity::model::application::{CommandInteraction, InputTextStyle, ResolvedOption, ResolvedValue};
+use serenity::model::id::{GuildId, RoleId};
+use serenity::utils::CreateQuickModal;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// What has to be typed to confirm the reset
+const RESET_CONFIRMATION: &str = "delete everything";
+
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
+	let Some(guild) = command.guild_id else {
+		bail!("Setup command was used outside of a guild");
+	};
+
+	let mut remove_partner_role = false;
+	let mut export = false;
+	for option in options.iter() {
+		let ResolvedValue::Boolean(value) = option.value else {
+			bail!("Non-boolean value passed to a boolean option");
+		};
+		match option.name {
+			"remove_partner_role" => remove_partner_role = value,
+			"export" => export = value,
+			_ => bail!("Invalid option passed to setup reset command: {}", option.name),
+		}
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let settings: Option<GuildSettings> = {
+		let mut db_connection = db_connection.lock().await;
+		guild_settings::table
+			.find(sql_guild_id)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?
+	};
+	let Some(settings) = settings else {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content(GUILD_NOT_SET_UP);
+		command
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	};
+
+	let confirmation_input = CreateInputText::new(
+		InputTextStyle::Short,
+		format!("Type \"{}\" to confirm", RESET_CONFIRMATION),
+		"",
+	)
+	.placeholder("This removes the partner list and all partner data")
+	.required(true);
+	let modal = CreateQuickModal::new("Reset Partner Manager")
+		.timeout(Duration::from_secs(300))
+		.field(confirmation_input);
+	let Some(modal_response) = command.quick_modal(ctx, modal).await.into_diagnostic()? else {
+		return Ok(());
+	};
+	let confirmation = modal_response.inputs.first().cloned().unwrap_or_default();
+	if !confirmation.trim().eq_ignore_ascii_case(RESET_CONFIRMATION) {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("The confirmation didn't match, so nothing was reset.");
+		modal_response
+			.interaction
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let message = CreateInteractionResponseMessage::new().content("Resetting this server's partner data...");
+	modal_response
+		.interaction
+		.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+		.await
+		.into_diagnostic()?;
+
+	// Everything the export needs is deleted below, so it has to be generated first
+	let export_data = if export {
+		let mut db_connection = db_connection.lock().await;
+		Some(build_export(&mut db_connection, &settings)?)
+	} else {
+		None
+	};
+
+	// Cleaning up in Discord can fail (for example, if permissions were removed), but the data is still deleted as
+	// requested. Failed steps are reported so they can be cleaned up by hand.
+	let mut failed_steps: Vec<&str> = Vec::new();
+
+	// The log channel setting goes away with everything else, so this is the last chance to log
+	let log_entry = log_embed(LogKind::Removed, "Partner Manager reset", Some(command.user.id));
+	if let Err(error) = send_staff_log(ctx, guild, log_entry).await {
+		eprintln!("Failed to log reset for guild {}: {:?}", guild, error);
+		failed_steps.push("logging the reset to the staff log");
+	}
+
+	if let Err(error) = remove_published_embeds(ctx, guild).await {
+		eprintln!("Failed to remove the partner list for guild {}: {:?}", guild, error);
+		failed_steps.push("removing the published partner list");
+	}
+	if let Err(error) = delete_publish_webhooks(ctx, guild).await {
+		eprintln!("Failed to delete publishing webhooks for guild {}: {:?}", guild, error);
+		failed_steps.push("deleting the webhooks used to publish the partner list");
+	}
+
+	let role_removal_counts = match (remove_partner_role, settings.partner_role) {
+		(true, Some(role)) => match remove_role_from_all(ctx, guild, RoleId::new(role as u64)).await {
+			Ok(counts) => Some(counts),
+			Err(error) => {
+				eprintln!("Failed to remove the partner role for guild {}: {:?}", guild, error);
+				failed_steps.push("removing the partner role from members");
+				None
+			}
+		},
+		_ => None,
+	};
+
+	purge_guild_data(ctx, guild).await?;
+
+	let mut reply = String::from("This server's partner data has been reset. Use `/setup start` to set up again.");
+	match role_removal_counts {
+		Some((removed, 0)) => reply.push_str(&format!("\nRemoved the partner role from {} members.", removed)),
+		Some((removed, failed)) => reply.push_str(&format!(
+			"\nRemoved the partner role from {} members. It couldn't be removed from {} members.",
+			removed, failed
+		)),
+		None => {
+			if remove_partner_role && settings.partner_role.is_none() {
+				reply.push_str("\nThere was no partner role to remove.");
+			}
+		}
+	}
+	if !failed_steps.is_empty() {
+		reply.push_str(&format!(
+			"\nSome cleanup in Discord failed and may need to be done by hand: {}.",
+			failed_steps.join(", ")
+		));
+	}
+	let mut message = EditInteractionResponse::new().content(reply);
+	if let Some(export_data) = export_data {
+		let attachment = CreateAttachment::bytes(export_data.into_bytes(), "partner-data.json");
+		message = message.new_attachment(attachment);
+	}
+	modal_response
+		.interaction
+		.edit_response(&ctx.http, message)
+		.await
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+/// Removes a role from every member who has it. Returns how many members it was removed from and how many it couldn't
+/// be removed from.
+async fn remove_role_from_all(ctx: &Context, guild: GuildId, role: RoleId) -> miette::Result<(usize, usize)> {
+	let mut removed_count = 0;
+	let mut failed_count = 0;
+	let mut members = guild.members_iter(&ctx.http).boxed();
+	while let Some(member) = members.next().await {
+		let member = member.into_diagnostic()?;
+		if !member.roles.contains(&role) {
+			continue;
+		}
+		match member.remove_role(&ctx.http, role).await {
+			Ok(_) => removed_count += 1,
+			Err(_) => failed_count += 1,
+		}
+	}
+	Ok((removed_count, failed_count))
+}
+
+/// Builds a JSON export of all the partner data stored for the guild
+fn build_export(db_connection: &mut PgConnection, settings: &GuildSettings) -> miette::Result<String> {
+	let sql_guild_id = settings.guild_id;
+	let categories: Vec<PartnerCategory> = partner_categories::table
+		.filter(partner_categories::guild_id.eq(sql_guild_id))
+		.load(db_connection)
+		.into_diagnostic()?;
+	let guild_partners: Vec<Partner> = partners::table
+		.filter(partners::guild.eq(sql_guild_id))
+		.order(partners::display_name.asc())
+		.load(db_connection)
+		.into_diagnostic()?;
+	let partnership_ids: Vec<&String> = guild_partners.iter().map(|partner| &partner.partnership_id).collect();
+	let reps: Vec<PartnerUser> = partner_users::table
+		.filter(partner_users::partnership_id.eq_any(&partnership_ids))
+		.load(db_connection)
+		.into_diagnostic()?;
+	let self_reps: Vec<PartnerSelfUser> = partner_self_users::table
+		.filter(partner_self_users::partnership.eq_any(&partnership_ids))
+		.load(db_connection)
+		.into_diagnostic()?;
+	let archived: Vec<ArchivedPartner> = archived_partners::table
+		.filter(archived_partners::guild.eq(sql_guild_id))
+		.order(archived_partners::display_name.asc())
+		.load(db_connection)
+		.into_diagnostic()?;
+	let archived_ids: Vec<&String> = archived.iter().map(|partner| &partner.partnership_id).collect();
+	let archived_reps: Vec<ArchivedPartnerUser> = archived_partner_users::table
+		.filter(archived_partner_users::partnership_id.eq_any(&archived_ids))
+		.load(db_connection)
+		.into_diagnostic()?;
+	let archived_self_reps: Vec<ArchivedPartnerSelfUser> = archived_partner_self_users::table
+		.filter(archived_partner_self_users::partnership.eq_any(&archived_ids))
+		.load(db_connection)
+		.into_diagnostic()?;
+	let embeds: Vec<EmbedData> = embed_data::table
+		.filter(embed_data::guild.eq(sql_guild_id))
+		.order(embed_data::embed_part_sequence_number.asc())
+		.load(db_connection)
+		.into_diagnostic()?;
+	let exempt_users: Vec<i64> = partner_role_exempt_users::table
+		.filter(partner_role_exempt_users::guild_id.eq(sql_guild_id))
+		.select(partner_role_exempt_users::user_id)
+		.load(db_connection)
+		.into_diagnostic()?;
+	let exempt_roles: Vec<i64> = partner_role_exempt_roles::table
+		.filter(partner_role_exempt_roles::guild_id.eq(sql_guild_id))
+		.select(partner_role_exempt_roles::role_id)
+		.load(db_connection)
+		.into_diagnostic()?;
+
+	// Discord IDs are exported as strings, since they don't fit in the numbers many JSON readers use
+	let category_values: Vec<Value> = categories
+		.iter()
+		.map(|category| json!({ "id": category.id, "name": category.name }))
+		.collect();
+	let partner_values: Vec<Value> = guild_partners
+		.iter()
+		.map(|partner| {
+			let partner_reps: Vec<String> = reps
+				.iter()
+				.filter(|rep| rep.partnership_id == partner.partnership_id)
+				.map(|rep| rep.user_id.to_string())
+				.collect();
+			let partner_self_reps: Vec<String> = self_reps
+				.iter()
+				.filter(|rep| rep.partnership == partner.partnership_id)
+				.map(|rep| rep.user_id.to_string())
+				.collect();
+			json!({
+				"id": partner.partnership_id,
+				"category": partner.category,
+				"partner_guild": partner.partner_guild.to_string(),
+				"partner_guild_name": partner.partner_guild_name,
+				"display_name": partner.display_name,
+				"invite_code": partner.invite_code,
+				"description": partner.description,
+				"emoji": partner.emoji,
+				"status": partner.status,
+				"representatives": partner_reps,
+				"self_representatives": partner_self_reps,
+			})
+		})
+		.collect();
+	let archived_values: Vec<Value> = archived
+		.iter()
+		.map(|partner| {
+			let partner_reps: Vec<String> = archived_reps
+				.iter()
+				.filter(|rep| rep.partnership_id == partner.partnership_id)
+				.map(|rep| rep.user_id.to_string())
+				.collect();
+			let partner_self_reps: Vec<String> = archived_self_reps
+				.iter()
+				.filter(|rep| rep.partnership == partner.partnership_id)
+				.map(|rep| rep.user_id.to_string())
+				.collect();
+			let removed_at = partner
+				.removed_at
+				.duration_since(UNIX_EPOCH)
+				.map(|since_epoch| since_epoch.as_secs())
+				.unwrap_or_default();
+			json!({
+				"id": partner.partnership_id,
+				"category": partner.category,
+				"partner_guild": partner.partner_guild.to_string(),
+				"partner_guild_name": partner.partner_guild_name,
+				"display_name": partner.display_name,
+				"invite_code": partner.invite_code,
+				"description": partner.description,
+				"emoji": partner.emoji,
+				"status": partner.status,
+				"removal_reason": partner.removal_reason,
+				"removed_at": removed_at,
+				"removed_by": partner.removed_by.map(|user| user.to_string()),
+				"representatives": partner_reps,
+				"self_representatives": partner_self_reps,
+			})
+		})
+		.collect();
+	let embed_values: Vec<Value> = embeds
+		.iter()
+		.map(|embed| {
+			json!({
+				"id": embed.id,
+				"position": embed.embed_part_sequence_number,
+				"name": embed.embed_name,
+				"category": embed.partner_category_list,
+				"channel": embed.channel.map(|channel| channel.to_string()),
+				"title": embed.title,
+				"title_url": embed.title_url,
+				"text": embed.embed_text,
+				"image_url": embed.image_url,
+				"thumbnail_url": embed.thumbnail_url,
+				"color": embed.color,
+				"author_name": embed.author_name,
+				"author_icon_url": embed.author_icon_url,
+				"footer_text": embed.footer_text,
+				"footer_icon_url": embed.footer_icon_url,
+				"show_timestamp": embed.show_timestamp,
+				"partner_line_template": embed.partner_line_template,
+			})
+		})
+		.collect();
+
+	let export = json!({
+		"guild_id": settings.guild_id.to_string(),
+		"settings": {
+			"embed_channel": settings.publish_channel.to_string(),
+			"partner_role": settings.partner_role.map(|role| role.to_string()),
+			"log_channel": settings.log_channel.map(|channel| channel.to_string()),
+			"announcement_channel": settings.announcement_channel.map(|channel| channel.to_string()),
+			"add_announcement_template": settings.add_announcement_template,
+			"remove_announcement_template": settings.remove_announcement_template,
+			"draft_mode": settings.draft_mode,
+			"republish_deleted_messages": settings.republish_deleted_messages,
+			"webhook_publishing": settings.webhook_publishing,
+			"webhook_username": settings.webhook_username,
+			"webhook_avatar_url": settings.webhook_avatar_url,
+			"crosspost_announcements": settings.crosspost_announcements,
+			"rep_changes_need_approval": settings.rep_changes_need_approval,
+		},
+		"partner_role_exempt_users": exempt_users.iter().map(|id| id.to_string()).collect::<Vec<String>>(),
+		"partner_role_exempt_roles": exempt_roles.iter().map(|id| id.to_string()).collect::<Vec<String>>(),
+		"categories": category_values,
+		"partners": partner_values,
+		"archived_partners": archived_values,
+		"embeds": embed_values,
+	});
+	serde_json::to_string_pretty(&export).into_diagnostic()
+}
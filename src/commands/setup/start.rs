@@ -1,40 +1,24 @@
 use crate::database::get_database_connection;
 use crate::models::GuildSettings;
 use crate::schema::guild_settings;
+use crate::sync::announcement::DEFAULT_ADD_ANNOUNCEMENT_TEMPLATE;
 use diesel::prelude::*;
 use diesel::result::{DatabaseErrorKind, Error as DieselError};
 use miette::{bail, ensure, IntoDiagnostic, Severity};
-use serenity::builder::{
-	CreateCommand, CreateCommandOption, CreateInteractionResponse, CreateInteractionResponseMessage,
-};
+use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
 use serenity::client::Context;
-use serenity::model::application::{CommandInteraction, CommandOptionType, CommandType, ResolvedValue};
+use serenity::model::application::{CommandInteraction, ResolvedOption, ResolvedValue};
 use serenity::model::channel::ChannelType;
-use serenity::model::permissions::Permissions;
 
-pub fn definition() -> CreateCommand {
-	CreateCommand::new("setup")
-		.kind(CommandType::ChatInput)
-		.default_member_permissions(Permissions::MANAGE_GUILD)
-		.dm_permission(false)
-		.description("Set up the bot for a particular guild")
-		.add_option(
-			CreateCommandOption::new(
-				CommandOptionType::Channel,
-				"embed_channel",
-				"The channel in which to show the partnership embed",
-			)
-			.required(true)
-			.channel_types(vec![ChannelType::Text]),
-		)
-}
-
-pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Result<()> {
+pub async fn execute(
+	ctx: &Context,
+	command: &CommandInteraction,
+	options: &[ResolvedOption<'_>],
+) -> miette::Result<()> {
 	let Some(guild) = command.guild_id else {
 		bail!("Setup command was used outside of a guild");
 	};
 
-	let options = command.data.options();
 	ensure!(
 		options.len() == 1,
 		severity = Severity::Error,
@@ -54,7 +38,10 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 	};
 
 	ensure!(
-		embed_channel.kind == ChannelType::Text,
+		matches!(
+			embed_channel.kind,
+			ChannelType::Text | ChannelType::News | ChannelType::Forum
+		),
 		severity = Severity::Error,
 		"wrong type of channel was entered for embed_channel ({:?})",
 		embed_channel.kind
@@ -80,13 +67,25 @@ pub async fn execute(ctx: &Context, command: &CommandInteraction) -> miette::Res
 		guild_id: guild.get() as i64,
 		publish_channel: embed_channel.id.get() as i64,
 		partner_role: None,
+		log_channel: None,
+		announcement_channel: None,
+		add_announcement_template: DEFAULT_ADD_ANNOUNCEMENT_TEMPLATE.to_string(),
+		remove_announcement_template: String::new(),
+		draft_mode: false,
+		republish_deleted_messages: true,
+		webhook_publishing: false,
+		webhook_username: String::new(),
+		webhook_avatar_url: String::new(),
+		crosspost_announcements: false,
+		departed_at: None,
+		rep_changes_need_approval: true,
 	};
 	let insert_result = diesel::insert_into(guild_settings::table)
 		.values(new_guild_settings)
 		.execute(&mut *db_connection);
 	let message = match insert_result {
 		Ok(_) => CreateInteractionResponseMessage::new().content(format!("Initial setup complete! Once fully configured, the partnership embed will be published to <#{}>.", embed_channel.id.get())),
-		Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => CreateInteractionResponseMessage::new().ephemeral(true).content("This server has already been set up. See `/settings` to modify individual settings or `/help` for other configuration."),
+		Err(DieselError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => CreateInteractionResponseMessage::new().ephemeral(true).content("This server has already been set up. See `/settings` to modify individual settings, or use `/setup reset` to start over."),
 		Err(error) => bail!(error)
 	};
 	command
@@ -1,7 +1,11 @@
 use knuffel::Decode;
 use miette::IntoDiagnostic;
+use std::time::Duration;
 use tokio::fs;
 
+/// How long data is kept for guilds the bot was removed from, if the config doesn't say
+const DEFAULT_DATA_RETENTION_DAYS: u64 = 30;
+
 pub async fn parse_config(config_path: &str) -> miette::Result<ConfigDocument> {
 	let config_file_contents = fs::read_to_string(config_path).await.into_diagnostic()?;
 	let config = knuffel::parse(config_path, &config_file_contents)?;
@@ -14,6 +18,16 @@ pub struct ConfigDocument {
 	pub discord_bot_token: String,
 	#[knuffel(child)]
 	pub database: DatabaseArgs,
+	#[knuffel(child, unwrap(argument))]
+	pub data_retention_days: Option<u64>,
+}
+
+impl ConfigDocument {
+	/// How long to keep a guild's data after the bot is removed from it, in case the bot is added back
+	pub fn data_retention(&self) -> Duration {
+		let days = self.data_retention_days.unwrap_or(DEFAULT_DATA_RETENTION_DAYS);
+		Duration::from_secs(days * 24 * 60 * 60)
+	}
 }
 
 #[derive(Debug, Decode)]
@@ -1,32 +1,114 @@
+use crate::commands::my_partnership::{handle_change_review_button, CHANGE_REVIEW_BUTTON_PREFIX};
+use crate::commands::partners::{handle_request_button, REQUEST_BUTTON_PREFIX};
+use crate::sync::embed_queue::{embed_update_worker, queue_deleted_message_republish, EmbedUpdateReceiver};
+use crate::sync::partner_names::{apply_suggested_name, check_partner_names_task, APPLY_NAME_BUTTON_PREFIX};
+use crate::sync::reconcile::reconcile_all_embeds_task;
+use crate::sync::retention::{
+	mark_guild_departed, mark_guild_present, mark_missing_guilds_departed, purge_departed_guilds_task,
+};
 use crate::sync::role::sync_all_roles_task;
 use serenity::async_trait;
 use serenity::model::application::{Command, Interaction};
 use serenity::model::gateway::Ready;
+use serenity::model::guild::{Guild, UnavailableGuild};
+use serenity::model::id::{ChannelId, GuildId, MessageId};
 use serenity::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
-pub struct Handler;
+pub struct Handler {
+	/// How long to keep data for guilds the bot was removed from
+	pub data_retention: Duration,
+	/// Whether the background tasks were started. `ready` is also sent when the bot reconnects, which mustn't start
+	/// another copy of each task.
+	pub background_tasks_started: AtomicBool,
+}
 
 #[async_trait]
 impl EventHandler for Handler {
 	async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-		if let Interaction::Command(command) = interaction {
-			let command_result = match command.data.name.as_str() {
-				"partner_categories" => crate::commands::partner_categories::execute(&ctx, &command).await,
-				"partner_embed" => crate::commands::partner_embed::execute(&ctx, &command).await,
-				"partners" => crate::commands::partners::execute(&ctx, &command).await,
-				"settings" => crate::commands::settings::execute(&ctx, &command).await,
-				"setup" => crate::commands::setup::execute(&ctx, &command).await,
-				_ => unimplemented!(),
-			};
-
-			if let Err(error) = command_result {
-				eprintln!("Command error: {}", error);
+		match interaction {
+			Interaction::Command(command) => {
+				let command_result = match command.data.name.as_str() {
+					"my_partnership" => crate::commands::my_partnership::execute(&ctx, &command).await,
+					"partner_categories" => crate::commands::partner_categories::execute(&ctx, &command).await,
+					"partner_embed" => crate::commands::partner_embed::execute(&ctx, &command).await,
+					"partners" => crate::commands::partners::execute(&ctx, &command).await,
+					"settings" => crate::commands::settings::execute(&ctx, &command).await,
+					"setup" => crate::commands::setup::execute(&ctx, &command).await,
+					_ => unimplemented!(),
+				};
+
+				if let Err(error) = command_result {
+					eprintln!("Command error: {}", error);
+				}
+			}
+			// Most components are handled by collectors in the commands that created them; only components on messages
+			// that outlive a command are handled here
+			Interaction::Component(component) if component.data.custom_id.starts_with(APPLY_NAME_BUTTON_PREFIX) => {
+				if let Err(error) = apply_suggested_name(&ctx, &component).await {
+					eprintln!("Failed to apply suggested partner name: {}", error);
+				}
+			}
+			Interaction::Component(component) if component.data.custom_id.starts_with(REQUEST_BUTTON_PREFIX) => {
+				if let Err(error) = handle_request_button(&ctx, &component).await {
+					eprintln!("Failed to handle partnership request response: {}", error);
+				}
+			}
+			Interaction::Component(component) if component.data.custom_id.starts_with(CHANGE_REVIEW_BUTTON_PREFIX) => {
+				if let Err(error) = handle_change_review_button(&ctx, &component).await {
+					eprintln!("Failed to review partner change: {}", error);
+				}
 			}
+			_ => (),
+		}
+	}
+
+	async fn guild_create(&self, ctx: Context, guild: Guild, _is_new: Option<bool>) {
+		if let Err(error) = mark_guild_present(&ctx, guild.id).await {
+			eprintln!("Failed to restore data for guild {}: {:?}", guild.id, error);
+		}
+	}
+
+	async fn guild_delete(&self, ctx: Context, incomplete: UnavailableGuild, _full: Option<Guild>) {
+		// Guilds become unavailable during Discord outages, but the bot is still in them
+		if incomplete.unavailable {
+			return;
+		}
+		if let Err(error) = mark_guild_departed(&ctx, incomplete.id).await {
+			eprintln!("Failed to mark guild {} as departed: {:?}", incomplete.id, error);
 		}
 	}
 
-	async fn ready(&self, ctx: Context, _data_about_bot: Ready) {
+	async fn message_delete(
+		&self,
+		ctx: Context,
+		_channel_id: ChannelId,
+		deleted_message_id: MessageId,
+		guild_id: Option<GuildId>,
+	) {
+		let Some(guild) = guild_id else {
+			return;
+		};
+		queue_deleted_message_republish(&ctx, guild, vec![deleted_message_id]).await;
+	}
+
+	async fn message_delete_bulk(
+		&self,
+		ctx: Context,
+		_channel_id: ChannelId,
+		multiple_deleted_messages_ids: Vec<MessageId>,
+		guild_id: Option<GuildId>,
+	) {
+		let Some(guild) = guild_id else {
+			return;
+		};
+		queue_deleted_message_republish(&ctx, guild, multiple_deleted_messages_ids).await;
+	}
+
+	async fn ready(&self, ctx: Context, data_about_bot: Ready) {
 		let commands = vec![
+			crate::commands::my_partnership::definition(),
 			crate::commands::partner_categories::definition(),
 			crate::commands::partner_embed::definition(),
 			crate::commands::partners::definition(),
@@ -37,6 +119,54 @@ impl EventHandler for Handler {
 			.await
 			.expect("Failed to register commands");
 
+		let present_guilds: Vec<GuildId> = data_about_bot.guilds.iter().map(|guild| guild.id).collect();
+		if let Err(error) = mark_missing_guilds_departed(&ctx, &present_guilds).await {
+			eprintln!("Failed to mark guilds the bot was removed from: {:?}", error);
+		}
+
+		if self.background_tasks_started.swap(true, Ordering::SeqCst) {
+			return;
+		}
+
+		let purge_ctx = ctx.clone();
+		let data_retention = self.data_retention;
+		tokio::task::spawn(async move {
+			let purge_result = purge_departed_guilds_task(&purge_ctx, data_retention).await;
+			if let Err(error) = purge_result {
+				eprintln!(
+					"Purging data for departed guilds failed; data will no longer be purged. {:?}",
+					error
+				);
+			}
+		});
+
+		let embed_update_receiver = ctx.data.write().await.remove::<EmbedUpdateReceiver>();
+		if let Some(embed_update_receiver) = embed_update_receiver {
+			let worker_ctx = ctx.clone();
+			tokio::task::spawn(async move {
+				embed_update_worker(&worker_ctx, embed_update_receiver).await;
+			});
+		}
+
+		let reconcile_ctx = ctx.clone();
+		tokio::task::spawn(async move {
+			let reconcile_result = reconcile_all_embeds_task(&reconcile_ctx).await;
+			if let Err(error) = reconcile_result {
+				eprintln!("Reconciling published embeds failed: {:?}", error);
+			}
+		});
+
+		let name_check_ctx = ctx.clone();
+		tokio::task::spawn(async move {
+			let name_check_result = check_partner_names_task(&name_check_ctx).await;
+			if let Err(error) = name_check_result {
+				eprintln!(
+					"Checking partner server names failed; renames will no longer be detected. {:?}",
+					error
+				);
+			}
+		});
+
 		tokio::task::spawn(async move {
 			let sync_result = sync_all_roles_task(&ctx).await;
 			if let Err(error) = sync_result {
@@ -1,7 +1,10 @@
 use miette::IntoDiagnostic;
 use serenity::client::Client;
 use serenity::model::gateway::GatewayIntents;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use sync::embed_queue::{EmbedUpdateQueue, EmbedUpdateReceiver};
+use tokio::sync::mpsc::unbounded_channel;
 use tokio::sync::Mutex;
 
 mod config;
@@ -27,16 +30,22 @@ async fn main() -> miette::Result<()> {
 	run_embedded_migrations(&mut db_connection)?;
 
 	let db_connection = Arc::new(Mutex::new(db_connection));
+	let (embed_update_sender, embed_update_receiver) = unbounded_channel();
 
-	let intents = GatewayIntents::GUILD_MEMBERS;
+	let intents = GatewayIntents::GUILDS | GatewayIntents::GUILD_MEMBERS | GatewayIntents::GUILD_MESSAGES;
 
 	let mut client = Client::builder(&config.discord_bot_token, intents)
-		.event_handler(Handler)
+		.event_handler(Handler {
+			data_retention: config.data_retention(),
+			background_tasks_started: AtomicBool::new(false),
+		})
 		.await
 		.into_diagnostic()?;
 	{
 		let mut data = client.data.write().await;
 		data.insert::<DatabaseConnection>(db_connection);
+		data.insert::<EmbedUpdateQueue>(embed_update_sender);
+		data.insert::<EmbedUpdateReceiver>(embed_update_receiver);
 	}
 
 	client.start().await.into_diagnostic()?;
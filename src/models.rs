@@ -1,7 +1,11 @@
 use crate::schema::{
-	embed_data, guild_settings, partner_categories, partner_self_users, partner_users, partners, published_messages,
+	archived_partner_self_users, archived_partner_users, archived_partners, embed_data, guild_settings,
+	partner_categories, partner_role_exempt_roles, partner_role_exempt_users, partner_self_users, partner_users,
+	partners, partnership_requests, pending_partner_changes, publish_webhooks, published_embed_summaries,
+	published_messages,
 };
 use diesel::prelude::*;
+use std::time::SystemTime;
 
 #[derive(Insertable, Queryable)]
 #[diesel(table_name = guild_settings)]
@@ -9,6 +13,18 @@ pub struct GuildSettings {
 	pub guild_id: i64,
 	pub publish_channel: i64,
 	pub partner_role: Option<i64>,
+	pub log_channel: Option<i64>,
+	pub announcement_channel: Option<i64>,
+	pub add_announcement_template: String,
+	pub remove_announcement_template: String,
+	pub draft_mode: bool,
+	pub republish_deleted_messages: bool,
+	pub webhook_publishing: bool,
+	pub webhook_username: String,
+	pub webhook_avatar_url: String,
+	pub crosspost_announcements: bool,
+	pub departed_at: Option<SystemTime>,
+	pub rep_changes_need_approval: bool,
 }
 
 #[derive(Insertable, Queryable)]
@@ -30,6 +46,16 @@ pub struct EmbedData {
 	pub embed_text: String,
 	pub image_url: String,
 	pub color: Option<i32>,
+	pub channel: Option<i64>,
+	pub title: String,
+	pub title_url: String,
+	pub thumbnail_url: String,
+	pub author_name: String,
+	pub author_icon_url: String,
+	pub footer_text: String,
+	pub footer_icon_url: String,
+	pub show_timestamp: bool,
+	pub partner_line_template: String,
 }
 
 #[derive(Insertable, Queryable)]
@@ -40,6 +66,13 @@ pub struct Partner {
 	pub partner_guild: i64,
 	pub display_name: String,
 	pub invite_code: String,
+	pub description: String,
+	pub emoji: String,
+	pub status: String,
+	/// The partner server's name as of when it was added or last checked, used to detect renames
+	pub partner_guild_name: String,
+	/// The partner server's approximate member count as of when its invite was last resolved
+	pub member_count: Option<i32>,
 }
 
 #[derive(Insertable, Queryable)]
@@ -49,9 +82,30 @@ pub struct PartnerUser {
 }
 
 #[derive(Insertable, Queryable)]
+#[diesel(table_name = published_embed_summaries)]
+pub struct PublishedEmbedSummary {
+	pub guild_id: i64,
+	pub embed_id: String,
+	pub summary: String,
+}
+
+#[derive(Clone, Insertable, Queryable)]
 pub struct PublishedMessage {
 	pub guild_id: i64,
 	pub message_id: i64,
+	pub channel_id: i64,
+	pub content_hash: Option<i64>,
+	pub webhook_id: Option<i64>,
+	pub webhook_identity_hash: Option<i64>,
+	pub thread_id: Option<i64>,
+}
+
+#[derive(Insertable, Queryable)]
+pub struct PublishWebhook {
+	pub channel_id: i64,
+	pub guild_id: i64,
+	pub webhook_id: i64,
+	pub webhook_url: String,
 }
 
 #[derive(Insertable, Queryable)]
@@ -59,3 +113,79 @@ pub struct PartnerSelfUser {
 	pub partnership: String,
 	pub user_id: i64,
 }
+
+#[derive(Insertable, Queryable)]
+pub struct PartnerRoleExemptUser {
+	pub guild_id: i64,
+	pub user_id: i64,
+}
+
+#[derive(Insertable, Queryable)]
+pub struct PartnerRoleExemptRole {
+	pub guild_id: i64,
+	pub role_id: i64,
+}
+
+#[derive(Insertable, Queryable)]
+pub struct ArchivedPartner {
+	pub partnership_id: String,
+	pub guild: i64,
+	pub category: Option<String>,
+	pub partner_guild: i64,
+	pub display_name: String,
+	pub invite_code: String,
+	pub description: String,
+	pub emoji: String,
+	pub status: String,
+	pub removal_reason: String,
+	pub removed_at: SystemTime,
+	pub removed_by: Option<i64>,
+	pub partner_guild_name: String,
+}
+
+#[derive(Insertable, Queryable)]
+pub struct ArchivedPartnerUser {
+	pub partnership_id: String,
+	pub user_id: i64,
+}
+
+#[derive(Insertable, Queryable)]
+pub struct ArchivedPartnerSelfUser {
+	pub partnership: String,
+	pub user_id: i64,
+}
+
+/// A request from one guild to partner with another, waiting for the other guild's staff to respond
+#[derive(Insertable, Queryable)]
+pub struct PartnershipRequest {
+	pub id: String,
+	pub requesting_guild: i64,
+	pub requested_guild: i64,
+	/// The category the requesting guild will list the requested guild in
+	pub requesting_category: String,
+	pub requester: i64,
+	pub requesting_guild_name: String,
+	/// The invite the requested guild will list for the requesting guild
+	pub requesting_invite_code: String,
+	pub requested_guild_name: String,
+	pub requested_invite_code: String,
+}
+
+/// A change to a partner's listing submitted by one of its representatives, waiting for staff approval. Fields that
+/// aren't being changed are `None`. Each partner has at most one pending change.
+#[derive(Insertable, Queryable)]
+pub struct PendingPartnerChange {
+	pub id: String,
+	pub partnership_id: String,
+	pub requested_by: i64,
+	pub display_name: Option<String>,
+	pub description: Option<String>,
+	pub invite_code: Option<String>,
+	/// The name of the server the new invite is for; set whenever `invite_code` is
+	pub partner_guild_name: Option<String>,
+	pub requested_at: SystemTime,
+	/// The channel of the staff log message for reviewing the change, once it's been sent
+	pub review_channel: Option<i64>,
+	/// The staff log message for reviewing the change, once it's been sent
+	pub review_message: Option<i64>,
+}
@@ -1,5 +1,37 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+	archived_partner_self_users (partnership, user_id) {
+		partnership -> Text,
+		user_id -> Int8,
+	}
+}
+
+diesel::table! {
+	archived_partner_users (partnership_id, user_id) {
+		partnership_id -> Text,
+		user_id -> Int8,
+	}
+}
+
+diesel::table! {
+	archived_partners (partnership_id) {
+		partnership_id -> Text,
+		guild -> Int8,
+		category -> Nullable<Text>,
+		partner_guild -> Int8,
+		display_name -> Text,
+		invite_code -> Text,
+		description -> Text,
+		emoji -> Text,
+		status -> Text,
+		removal_reason -> Text,
+		removed_at -> Timestamp,
+		removed_by -> Nullable<Int8>,
+		partner_guild_name -> Text,
+	}
+}
+
 diesel::table! {
 	embed_data (id) {
 		id -> Text,
@@ -10,6 +42,16 @@ diesel::table! {
 		embed_text -> Text,
 		image_url -> Text,
 		color -> Nullable<Int4>,
+		channel -> Nullable<Int8>,
+		title -> Text,
+		title_url -> Text,
+		thumbnail_url -> Text,
+		author_name -> Text,
+		author_icon_url -> Text,
+		footer_text -> Text,
+		footer_icon_url -> Text,
+		show_timestamp -> Bool,
+		partner_line_template -> Text,
 	}
 }
 
@@ -18,6 +60,18 @@ diesel::table! {
 		guild_id -> Int8,
 		publish_channel -> Int8,
 		partner_role -> Nullable<Int8>,
+		log_channel -> Nullable<Int8>,
+		announcement_channel -> Nullable<Int8>,
+		add_announcement_template -> Text,
+		remove_announcement_template -> Text,
+		draft_mode -> Bool,
+		republish_deleted_messages -> Bool,
+		webhook_publishing -> Bool,
+		webhook_username -> Text,
+		webhook_avatar_url -> Text,
+		crosspost_announcements -> Bool,
+		departed_at -> Nullable<Timestamp>,
+		rep_changes_need_approval -> Bool,
 	}
 }
 
@@ -29,6 +83,20 @@ diesel::table! {
 	}
 }
 
+diesel::table! {
+	partner_role_exempt_roles (guild_id, role_id) {
+		guild_id -> Int8,
+		role_id -> Int8,
+	}
+}
+
+diesel::table! {
+	partner_role_exempt_users (guild_id, user_id) {
+		guild_id -> Int8,
+		user_id -> Int8,
+	}
+}
+
 diesel::table! {
 	partner_self_users (partnership, user_id) {
 		partnership -> Text,
@@ -51,6 +119,57 @@ diesel::table! {
 		partner_guild -> Int8,
 		display_name -> Text,
 		invite_code -> Text,
+		description -> Text,
+		emoji -> Text,
+		status -> Text,
+		partner_guild_name -> Text,
+		member_count -> Nullable<Int4>,
+	}
+}
+
+diesel::table! {
+	partnership_requests (id) {
+		id -> Text,
+		requesting_guild -> Int8,
+		requested_guild -> Int8,
+		requesting_category -> Text,
+		requester -> Int8,
+		requesting_guild_name -> Text,
+		requesting_invite_code -> Text,
+		requested_guild_name -> Text,
+		requested_invite_code -> Text,
+	}
+}
+
+diesel::table! {
+	pending_partner_changes (id) {
+		id -> Text,
+		partnership_id -> Text,
+		requested_by -> Int8,
+		display_name -> Nullable<Text>,
+		description -> Nullable<Text>,
+		invite_code -> Nullable<Text>,
+		partner_guild_name -> Nullable<Text>,
+		requested_at -> Timestamp,
+		review_channel -> Nullable<Int8>,
+		review_message -> Nullable<Int8>,
+	}
+}
+
+diesel::table! {
+	publish_webhooks (channel_id) {
+		channel_id -> Int8,
+		guild_id -> Int8,
+		webhook_id -> Int8,
+		webhook_url -> Text,
+	}
+}
+
+diesel::table! {
+	published_embed_summaries (guild_id, embed_id) {
+		guild_id -> Int8,
+		embed_id -> Text,
+		summary -> Text,
 	}
 }
 
@@ -58,24 +177,48 @@ diesel::table! {
 	published_messages (guild_id, message_id) {
 		guild_id -> Int8,
 		message_id -> Int8,
+		channel_id -> Int8,
+		content_hash -> Nullable<Int8>,
+		webhook_id -> Nullable<Int8>,
+		webhook_identity_hash -> Nullable<Int8>,
+		thread_id -> Nullable<Int8>,
 	}
 }
 
 diesel::joinable!(embed_data -> guild_settings (guild));
+diesel::joinable!(archived_partner_self_users -> archived_partners (partnership));
+diesel::joinable!(archived_partner_users -> archived_partners (partnership_id));
+diesel::joinable!(archived_partners -> guild_settings (guild));
+diesel::joinable!(archived_partners -> partner_categories (category));
 diesel::joinable!(embed_data -> partner_categories (partner_category_list));
 diesel::joinable!(partner_categories -> guild_settings (guild_id));
+diesel::joinable!(partner_role_exempt_roles -> guild_settings (guild_id));
+diesel::joinable!(partner_role_exempt_users -> guild_settings (guild_id));
 diesel::joinable!(partner_self_users -> partners (partnership));
 diesel::joinable!(partner_users -> partners (partnership_id));
 diesel::joinable!(partners -> guild_settings (guild));
 diesel::joinable!(partners -> partner_categories (category));
+diesel::joinable!(partnership_requests -> partner_categories (requesting_category));
+diesel::joinable!(pending_partner_changes -> partners (partnership_id));
+diesel::joinable!(publish_webhooks -> guild_settings (guild_id));
+diesel::joinable!(published_embed_summaries -> guild_settings (guild_id));
 diesel::joinable!(published_messages -> guild_settings (guild_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+	archived_partner_self_users,
+	archived_partner_users,
+	archived_partners,
 	embed_data,
 	guild_settings,
 	partner_categories,
+	partner_role_exempt_roles,
+	partner_role_exempt_users,
 	partner_self_users,
 	partner_users,
 	partners,
+	partnership_requests,
+	pending_partner_changes,
+	publish_webhooks,
+	published_embed_summaries,
 	published_messages,
 );
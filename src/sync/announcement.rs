@@ -0,0 +1,132 @@
+use crate::models::{GuildSettings, Partner};
+use crate::schema::{guild_settings, partner_categories, partner_users, partners};
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
+use diesel::prelude::*;
+use miette::IntoDiagnostic;
+use serenity::builder::{CreateAllowedMentions, CreateMessage};
+use serenity::client::Context;
+use serenity::model::id::{ChannelId, GuildId, UserId};
+
+pub const DEFAULT_ADD_ANNOUNCEMENT_TEMPLATE: &str = "Welcome our new partner, {name}! {invite}";
+
+/// The placeholders that can be used in new partner announcement templates, for display to users. Partners often
+/// don't have representatives yet when they're added, so `{reps}` is only available for removals.
+pub const ADD_ANNOUNCEMENT_PLACEHOLDERS: &str = "{name}, {invite}, {category}";
+
+/// The placeholders that can be used in removed partner announcement templates, for display to users
+pub const REMOVE_ANNOUNCEMENT_PLACEHOLDERS: &str = "{name}, {invite}, {category}, {reps}";
+
+/// Discord's limit on the length of a message's content
+const MAX_MESSAGE_LENGTH: usize = 2000;
+
+pub enum AnnouncementKind {
+	Added,
+	Removed,
+}
+
+/// A rendered partner announcement, ready to be sent
+pub struct PartnerAnnouncement {
+	guild: GuildId,
+	channel: ChannelId,
+	partner_name: String,
+	content: String,
+	reps: Vec<UserId>,
+}
+
+impl PartnerAnnouncement {
+	/// Sends the announcement. Failing to announce shouldn't prevent the partner change, so a failed send is reported
+	/// to the staff log instead of returned.
+	pub async fn send(self, ctx: &Context) -> miette::Result<()> {
+		let message = CreateMessage::new()
+			.content(self.content)
+			.allowed_mentions(CreateAllowedMentions::new().users(self.reps));
+
+		if let Err(error) = self.channel.send_message(&ctx.http, message).await {
+			let embed = log_embed(LogKind::Failed, "Partner announcement failed", None)
+				.field("Partner", self.partner_name, true)
+				.field("Channel", format!("<#{}>", self.channel.get()), true)
+				.description(format!("```\n{}\n```", error));
+			send_staff_log(ctx, self.guild, embed).await?;
+		}
+		Ok(())
+	}
+}
+
+/// Fills in the placeholders of an announcement template with the partner's data. Representatives are only filled
+/// in if they're given. The result is cut off at Discord's message length limit.
+pub fn render_announcement(template: &str, partner: &Partner, category_name: &str, reps: Option<&[UserId]>) -> String {
+	let mut content = template
+		.replace("{name}", &partner.display_name)
+		.replace("{invite}", &format!("https://discord.gg/{}", partner.invite_code))
+		.replace("{category}", category_name);
+	if let Some(reps) = reps {
+		let reps = if reps.is_empty() {
+			String::from("none")
+		} else {
+			let rep_mentions: Vec<String> = reps.iter().map(|rep| format!("<@{}>", rep.get())).collect();
+			rep_mentions.join(", ")
+		};
+		content = content.replace("{reps}", &reps);
+	}
+
+	if content.chars().count() > MAX_MESSAGE_LENGTH {
+		content = content.chars().take(MAX_MESSAGE_LENGTH - 1).collect();
+		content.push('…');
+	}
+	content
+}
+
+/// Builds the announcement for a partner change. Returns `None` if the guild doesn't announce that kind of change.
+/// For removals, this must be called before the partner is deleted.
+pub fn build_partner_announcement(
+	db_connection: &mut PgConnection,
+	guild: GuildId,
+	kind: AnnouncementKind,
+	partnership_id: &str,
+) -> miette::Result<Option<PartnerAnnouncement>> {
+	let sql_guild_id = guild.get() as i64;
+	let guild_settings: GuildSettings = guild_settings::table
+		.find(sql_guild_id)
+		.first(db_connection)
+		.into_diagnostic()?;
+	let Some(channel) = guild_settings.announcement_channel else {
+		return Ok(None);
+	};
+	let template = match kind {
+		AnnouncementKind::Added => guild_settings.add_announcement_template,
+		AnnouncementKind::Removed => guild_settings.remove_announcement_template,
+	};
+	if template.is_empty() {
+		return Ok(None);
+	}
+
+	let partner: Partner = partners::table
+		.find(partnership_id)
+		.first(db_connection)
+		.into_diagnostic()?;
+	let category_name: String = partner_categories::table
+		.find(&partner.category)
+		.select(partner_categories::name)
+		.first(db_connection)
+		.into_diagnostic()?;
+	let reps: Option<Vec<UserId>> = match kind {
+		AnnouncementKind::Added => None,
+		AnnouncementKind::Removed => {
+			let reps: Vec<i64> = partner_users::table
+				.filter(partner_users::partnership_id.eq(partnership_id))
+				.select(partner_users::user_id)
+				.load(db_connection)
+				.into_diagnostic()?;
+			Some(reps.into_iter().map(|id| UserId::new(id as u64)).collect())
+		}
+	};
+
+	let content = render_announcement(&template, &partner, &category_name, reps.as_deref());
+	Ok(Some(PartnerAnnouncement {
+		guild,
+		channel: ChannelId::new(channel as u64),
+		partner_name: partner.display_name,
+		content,
+		reps: reps.unwrap_or_default(),
+	}))
+}
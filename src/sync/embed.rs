@@ -1,69 +1,301 @@
 use crate::database::get_database_connection;
-use crate::models::{EmbedData, Partner, PublishedMessage};
-use crate::schema::{embed_data, guild_settings, partners, published_messages};
+use crate::models::{EmbedData, GuildSettings, Partner, PublishWebhook, PublishedEmbedSummary, PublishedMessage};
+use crate::schema::{
+	embed_data, guild_settings, partner_categories, partners, publish_webhooks, published_embed_summaries,
+	published_messages,
+};
+use crate::utils::partner_status::PartnerStatus;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
-use miette::IntoDiagnostic;
-use serenity::builder::{CreateEmbed, CreateMessage, EditMessage};
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{
+	CreateEmbed, CreateEmbedAuthor, CreateEmbedFooter, CreateForumPost, CreateMessage, CreateWebhook, EditMessage,
+	EditThread, EditWebhookMessage, ExecuteWebhook,
+};
 use serenity::client::Context;
+use serenity::http::{ErrorResponse, HttpError, StatusCode};
+use serenity::model::channel::ChannelType;
 use serenity::model::id::{ChannelId, GuildId, MessageId};
+use serenity::model::webhook::Webhook;
+use serenity::model::Timestamp;
+use serenity::prelude::SerenityError;
 
+pub const DEFAULT_PARTNER_LINE_TEMPLATE: &str = "- [{name}]({invite})";
+
+/// The placeholders that can be used in partner line templates, for display to users
+pub const PARTNER_LINE_PLACEHOLDERS: &str =
+	"{number}, {emoji}, {name}, {invite}, {description}, {category}, {member_count}";
+
+/// The name given to the webhooks created to publish partner lists
+const PUBLISH_WEBHOOK_NAME: &str = "Partner List";
+
+/// The name given to the posts created to publish partner lists in forum channels
+const FORUM_POST_NAME: &str = "Partners";
+
+/// Discord's limit on the total length of the text in an embed, which also applies across all embeds in a message
+const MAX_EMBED_LENGTH: usize = 6000;
+
+/// Discord's limit on the number of embeds in a message
+const MAX_MESSAGE_EMBEDS: usize = 10;
+
+/// Discord's limit on the length of an embed field's value
+const MAX_FIELD_LENGTH: usize = 1024;
+
+/// Discord's limit on the number of fields in an embed
+const MAX_FIELDS: usize = 25;
+
+/// The length reserved for the line noting partners that didn't fit in an embed
+const OVERFLOW_LINE_RESERVE: usize = 20;
+
+/// Removes the guild's published messages from Discord. Messages that couldn't be deleted stay tracked. Only the
+/// update worker may call this, through [`remove_published_embeds`](crate::sync::embed_queue::remove_published_embeds).
 pub async fn remove_embed(ctx: &Context, guild: GuildId) -> miette::Result<()> {
 	let db_connection = get_database_connection(ctx).await;
 	let sql_guild_id = guild.get() as i64;
 
-	let (channel, messages) = {
+	let (messages, webhooks) = {
 		let mut db_connection = db_connection.lock().await;
-
 		let messages: Vec<PublishedMessage> = published_messages::table
 			.filter(published_messages::guild_id.eq(sql_guild_id))
 			.load(&mut *db_connection)
 			.into_diagnostic()?;
-		let channel_id: i64 = guild_settings::table
+		let webhooks: Vec<PublishWebhook> = publish_webhooks::table
+			.filter(publish_webhooks::guild_id.eq(sql_guild_id))
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		(messages, webhooks)
+	};
+
+	// Ignore permission errors, leaving the messages that couldn't be deleted tracked
+	let failed_deletes = delete_published_messages(ctx, &webhooks, &messages).await;
+	let kept_messages: Vec<i64> = failed_deletes.into_iter().map(|(message_id, _)| message_id).collect();
+
+	let mut db_connection = db_connection.lock().await;
+	diesel::delete(published_embed_summaries::table)
+		.filter(published_embed_summaries::guild_id.eq(sql_guild_id))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+	diesel::delete(published_messages::table)
+		.filter(
+			published_messages::guild_id
+				.eq(sql_guild_id)
+				.and(published_messages::message_id.ne_all(kept_messages)),
+		)
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+/// Moves the partner list to a new default publish channel, deleting the messages published to the old default channel
+/// so they're posted again in the new channel on the next publish. Embeds with their own channel are left alone. If any
+/// messages couldn't be deleted, the channel isn't changed and the errors are returned. Only the update worker may call
+/// this, through [`move_default_publish_channel`](crate::sync::embed_queue::move_default_publish_channel).
+pub async fn move_default_channel(
+	ctx: &Context,
+	guild: GuildId,
+	new_channel: ChannelId,
+) -> miette::Result<Vec<SerenityError>> {
+	let db_connection = get_database_connection(ctx).await;
+	let sql_guild_id = guild.get() as i64;
+
+	let (old_channel_id, messages, webhooks) = {
+		let mut db_connection = db_connection.lock().await;
+		let old_channel_id: i64 = guild_settings::table
 			.find(sql_guild_id)
 			.select(guild_settings::publish_channel)
 			.first(&mut *db_connection)
 			.into_diagnostic()?;
+		let messages: Vec<PublishedMessage> = published_messages::table
+			.filter(
+				published_messages::guild_id
+					.eq(sql_guild_id)
+					.and(published_messages::channel_id.eq(old_channel_id)),
+			)
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		let webhooks: Vec<PublishWebhook> = publish_webhooks::table
+			.filter(publish_webhooks::guild_id.eq(sql_guild_id))
+			.load(&mut *db_connection)
+			.into_diagnostic()?;
+		(old_channel_id, messages, webhooks)
+	};
+
+	let message_delete_errors: Vec<SerenityError> = delete_published_messages(ctx, &webhooks, &messages)
+		.await
+		.into_iter()
+		.map(|(_, error)| error)
+		.collect();
 
-		(ChannelId::new(channel_id as u64), messages)
+	let mut db_connection = db_connection.lock().await;
+	// Messages that couldn't be deleted are left for staff to delete by hand, so they're no longer tracked either way
+	diesel::delete(published_messages::table)
+		.filter(
+			published_messages::guild_id
+				.eq(sql_guild_id)
+				.and(published_messages::channel_id.eq(old_channel_id)),
+		)
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+	if !message_delete_errors.is_empty() {
+		return Ok(message_delete_errors);
+	}
+
+	diesel::update(guild_settings::table)
+		.filter(guild_settings::guild_id.eq(sql_guild_id))
+		.set(guild_settings::publish_channel.eq(new_channel.get() as i64))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+
+	Ok(Vec::new())
+}
+
+/// Deletes the webhooks created to publish the guild's partner list
+pub async fn delete_publish_webhooks(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+	let db_connection = get_database_connection(ctx).await;
+	let sql_guild_id = guild.get() as i64;
+	let webhooks: Vec<PublishWebhook> = {
+		let mut db_connection = db_connection.lock().await;
+		publish_webhooks::table
+			.filter(publish_webhooks::guild_id.eq(sql_guild_id))
+			.load(&mut *db_connection)
+			.into_diagnostic()?
 	};
 
-	let mut successfully_removed_messages: Vec<u64> = Vec::new();
-	for message in messages {
-		let message_id = message.message_id as u64;
+	let mut deleted_channels: Vec<i64> = Vec::new();
+	for publish_webhook in webhooks {
+		let delete_result = match Webhook::from_url(&ctx.http, &publish_webhook.webhook_url).await {
+			Ok(webhook) => webhook.delete(&ctx.http).await,
+			Err(error) => Err(error),
+		};
+		match delete_result {
+			Ok(_) => deleted_channels.push(publish_webhook.channel_id),
+			Err(error) if is_not_found(&error) => deleted_channels.push(publish_webhook.channel_id),
+			// Ignore permission errors
+			Err(_) => (),
+		}
+	}
 
-		// Ignore permission errors
-		if channel.delete_message(&ctx.http, message_id).await.is_ok() {
-			successfully_removed_messages.push(message_id);
+	let mut db_connection = db_connection.lock().await;
+	diesel::delete(publish_webhooks::table)
+		.filter(publish_webhooks::channel_id.eq_any(deleted_channels))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+/// Publishes changes to the guild's embeds, unless the guild is in draft mode. In draft mode, changes are held until
+/// they're published with [`publish_changes`].
+pub async fn update_embed(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+	let db_connection = get_database_connection(ctx).await;
+	let draft_mode: Option<bool> = {
+		let mut db_connection = db_connection.lock().await;
+		guild_settings::table
+			.find(guild.get() as i64)
+			.select(guild_settings::draft_mode)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?
+	};
+
+	// Updates queued before the guild was reset have nothing left to publish
+	let Some(draft_mode) = draft_mode else {
+		return Ok(());
+	};
+	if draft_mode {
+		return Ok(());
+	}
+
+	publish_changes(ctx, guild).await
+}
+
+/// Republishes the guild's embeds if any of the deleted messages are published embed messages, unless the guild has
+/// opted out of republishing deleted messages. Only the update worker may call this, through
+/// [`queue_deleted_message_republish`](crate::sync::embed_queue::queue_deleted_message_republish); since the worker
+/// also deletes published messages itself, their rows are already gone by the time their deletions are checked here.
+pub async fn republish_deleted_messages(
+	ctx: &Context,
+	guild: GuildId,
+	message_ids: &[MessageId],
+) -> miette::Result<()> {
+	let db_connection = get_database_connection(ctx).await;
+	let sql_guild_id = guild.get() as i64;
+	let sql_message_ids: Vec<i64> = message_ids.iter().map(|id| id.get() as i64).collect();
+	{
+		let mut db_connection = db_connection.lock().await;
+		let republish: Option<bool> = guild_settings::table
+			.find(sql_guild_id)
+			.select(guild_settings::republish_deleted_messages)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		if republish != Some(true) {
+			return Ok(());
+		}
+
+		// Clearing the hash makes sure the message is edited, which resends it when the edit finds it missing
+		let tracked_count = diesel::update(published_messages::table)
+			.filter(
+				published_messages::guild_id
+					.eq(sql_guild_id)
+					.and(published_messages::message_id.eq_any(&sql_message_ids)),
+			)
+			.set(published_messages::content_hash.eq(None::<i64>))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?;
+		if tracked_count == 0 {
+			return Ok(());
 		}
 	}
 
-	let delete_message: Vec<i64> = successfully_removed_messages.into_iter().map(|id| id as i64).collect();
+	update_embed(ctx, guild).await
+}
+
+/// Stops tracking published messages that no longer exist. Only the update worker may call this, through
+/// [`queue_forget_published_messages`](crate::sync::embed_queue::queue_forget_published_messages).
+pub async fn forget_published_messages(ctx: &Context, guild: GuildId, message_ids: &[MessageId]) -> miette::Result<()> {
+	let sql_message_ids: Vec<i64> = message_ids.iter().map(|id| id.get() as i64).collect();
+	let db_connection = get_database_connection(ctx).await;
 	let mut db_connection = db_connection.lock().await;
 	diesel::delete(published_messages::table)
 		.filter(
 			published_messages::guild_id
-				.eq(sql_guild_id)
-				.and(published_messages::message_id.eq_any(delete_message)),
+				.eq(guild.get() as i64)
+				.and(published_messages::message_id.eq_any(&sql_message_ids)),
 		)
 		.execute(&mut *db_connection)
 		.into_diagnostic()?;
-
 	Ok(())
 }
 
-pub async fn update_embed(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+/// Publishes the current state of the guild's embeds, regardless of draft mode
+pub async fn publish_changes(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+	let publish_result = publish_embed(ctx, guild).await;
+	if let Err(error) = &publish_result {
+		let embed = log_embed(LogKind::Failed, "Publishing the partner list failed", None)
+			.description(format!("```\n{}\n```", error));
+		send_staff_log(ctx, guild, embed).await?;
+	}
+	publish_result
+}
+
+async fn publish_embed(ctx: &Context, guild: GuildId) -> miette::Result<()> {
 	let db_connection = get_database_connection(ctx).await;
 	let sql_guild_id = guild.get() as i64;
 	let mut db_connection = db_connection.lock().await;
 
-	let channel_id: i64 = guild_settings::table
+	let settings: GuildSettings = guild_settings::table
 		.find(sql_guild_id)
-		.select(guild_settings::publish_channel)
 		.first(&mut *db_connection)
 		.into_diagnostic()?;
-	let channel_id = ChannelId::new(channel_id as u64);
+	let webhooks: Vec<PublishWebhook> = publish_webhooks::table
+		.filter(publish_webhooks::guild_id.eq(sql_guild_id))
+		.load(&mut *db_connection)
+		.into_diagnostic()?;
 	let existing_messages: Vec<PublishedMessage> = published_messages::table
 		.filter(published_messages::guild_id.eq(sql_guild_id))
+		.order(published_messages::message_id.asc())
 		.load(&mut *db_connection)
 		.into_diagnostic()?;
 	let embed_data: Vec<EmbedData> = embed_data::table
@@ -71,122 +303,915 @@ pub async fn update_embed(ctx: &Context, guild: GuildId) -> miette::Result<()> {
 		.order(embed_data::embed_part_sequence_number.asc())
 		.load(&mut *db_connection)
 		.into_diagnostic()?;
+	let channel_messages = build_channel_messages(&mut db_connection, guild, embed_data)?;
+
+	let (current_messages, stale_messages): (Vec<PublishedMessage>, Vec<PublishedMessage>) =
+		existing_messages.into_iter().partition(|message| {
+			channel_messages
+				.iter()
+				.any(|(channel, _)| channel.get() == message.channel_id as u64)
+		});
+	if !stale_messages.is_empty() {
+		// Ignore permission errors
+		let _ = delete_published_messages(ctx, &webhooks, &stale_messages).await;
+		let stale_message_ids: Vec<i64> = stale_messages.iter().map(|message| message.message_id).collect();
+		diesel::delete(published_messages::table)
+			.filter(
+				published_messages::guild_id
+					.eq(sql_guild_id)
+					.and(published_messages::message_id.eq_any(&stale_message_ids)),
+			)
+			.execute(&mut *db_connection)
+			.into_diagnostic()?;
+	}
+
+	for (channel_id, embed_groups) in channel_messages {
+		let existing_channel_messages: Vec<PublishedMessage> = current_messages
+			.iter()
+			.filter(|message| message.channel_id as u64 == channel_id.get())
+			.cloned()
+			.collect();
+		let publisher = channel_publisher(ctx, &mut db_connection, &settings, channel_id).await?;
+
+		// Messages sent another way (such as by the bot instead of a webhook, or with a different name or avatar) can't be
+		// edited to match, so the whole channel is sent again to keep the messages in order
+		let mut existing_channel_messages = if existing_channel_messages.iter().all(|message| publisher.sent(message)) {
+			existing_channel_messages
+		} else {
+			// Ignore permission errors
+			let _ = delete_published_messages(ctx, &webhooks, &existing_channel_messages).await;
+			let replaced_message_ids: Vec<i64> = existing_channel_messages
+				.iter()
+				.map(|message| message.message_id)
+				.collect();
+			diesel::delete(published_messages::table)
+				.filter(
+					published_messages::guild_id
+						.eq(sql_guild_id)
+						.and(published_messages::message_id.eq_any(&replaced_message_ids)),
+				)
+				.execute(&mut *db_connection)
+				.into_diagnostic()?;
+			Vec::new()
+		};
+
+		let channel_kind = channel_id
+			.to_channel(&ctx.http)
+			.await
+			.into_diagnostic()?
+			.guild()
+			.map(|channel| channel.kind)
+			.unwrap_or(ChannelType::Text);
+		let mut target = PublishTarget {
+			channel_id,
+			thread_id: None,
+			crosspost: settings.crosspost_announcements && channel_kind == ChannelType::News,
+		};
+		if channel_kind == ChannelType::Forum {
+			let existing_thread = existing_channel_messages.iter().find_map(|message| message.thread_id);
+			target.thread_id = open_forum_post(ctx, existing_thread).await?;
+			if target.thread_id.is_none() {
+				// The post is gone along with its messages, so a new post is started with the first message
+				let gone_message_ids: Vec<i64> = existing_channel_messages
+					.iter()
+					.map(|message| message.message_id)
+					.collect();
+				diesel::delete(published_messages::table)
+					.filter(
+						published_messages::guild_id
+							.eq(sql_guild_id)
+							.and(published_messages::message_id.eq_any(&gone_message_ids)),
+					)
+					.execute(&mut *db_connection)
+					.into_diagnostic()?;
+
+				let Some(first_embed_group) = embed_groups.first() else {
+					continue;
+				};
+				let (thread_id, message_id) = publisher
+					.create_forum_post(ctx, channel_id, first_embed_group.clone())
+					.await?;
+				let post_message = PublishedMessage {
+					guild_id: sql_guild_id,
+					message_id: message_id.get() as i64,
+					channel_id: channel_id.get() as i64,
+					content_hash: Some(embed_group_hash(first_embed_group)),
+					webhook_id: publisher.webhook_id(),
+					webhook_identity_hash: publisher.identity_hash(),
+					thread_id: Some(thread_id.get() as i64),
+				};
+				diesel::insert_into(published_messages::table)
+					.values(post_message.clone())
+					.execute(&mut *db_connection)
+					.into_diagnostic()?;
+				existing_channel_messages = vec![post_message];
+				target.thread_id = Some(thread_id);
+			}
+		}
+
+		publish_to_channel(
+			ctx,
+			&mut db_connection,
+			guild,
+			&target,
+			&publisher,
+			embed_groups,
+			existing_channel_messages,
+		)
+		.await?;
+	}
+
+	let summaries = build_embed_summaries(&mut db_connection, guild)?;
+	db_connection
+		.transaction(|db_connection| {
+			diesel::delete(published_embed_summaries::table)
+				.filter(published_embed_summaries::guild_id.eq(sql_guild_id))
+				.execute(db_connection)?;
+			diesel::insert_into(published_embed_summaries::table)
+				.values(summaries)
+				.execute(db_connection)?;
+			Ok::<(), diesel::result::Error>(())
+		})
+		.into_diagnostic()?;
+
+	Ok(())
+}
+
+/// Builds a text summary of each of the guild's embeds as they would currently be published. These summaries are
+/// stored when embeds are published so pending changes can be compared against what's published.
+pub fn build_embed_summaries(
+	db_connection: &mut PgConnection,
+	guild: GuildId,
+) -> miette::Result<Vec<PublishedEmbedSummary>> {
+	let sql_guild_id = guild.get() as i64;
+	let default_channel_id: i64 = guild_settings::table
+		.find(sql_guild_id)
+		.select(guild_settings::publish_channel)
+		.first(db_connection)
+		.into_diagnostic()?;
+	let embed_data: Vec<EmbedData> = embed_data::table
+		.filter(embed_data::guild.eq(sql_guild_id))
+		.order(embed_data::embed_part_sequence_number.asc())
+		.load(db_connection)
+		.into_diagnostic()?;
+
+	let mut summaries: Vec<PublishedEmbedSummary> = Vec::with_capacity(embed_data.len());
+	for (position, embed) in embed_data.into_iter().enumerate() {
+		let mut summary_lines = vec![
+			format!("Name: {}", embed.embed_name),
+			format!("Position: {}", position + 1),
+			format!("Channel: {}", embed.channel.unwrap_or(default_channel_id)),
+		];
+		if let Some(color) = embed.color {
+			summary_lines.push(format!("Color: {:06x}", color));
+		}
+		let details = [
+			("Title", &embed.title),
+			("Title link", &embed.title_url),
+			("Author", &embed.author_name),
+			("Author icon", &embed.author_icon_url),
+			("Thumbnail", &embed.thumbnail_url),
+			("Image", &embed.image_url),
+			("Footer", &embed.footer_text),
+			("Footer icon", &embed.footer_icon_url),
+		];
+		for (label, value) in details {
+			if !value.is_empty() {
+				summary_lines.push(format!("{}: {}", label, value));
+			}
+		}
+		if embed.show_timestamp {
+			summary_lines.push(String::from("Timestamp: shown"));
+		}
+		for text_line in embed.embed_text.lines() {
+			summary_lines.push(format!("Text: {}", text_line));
+		}
+		if let Some(partner_category) = &embed.partner_category_list {
+			// Member counts change on their own, so they're left out of the summaries
+			let (category_name, partners) = category_partners(partner_category, db_connection)?;
+			for partner_line in render_partner_lines(&embed.partner_line_template, &category_name, &partners, false) {
+				summary_lines.push(format!("Partner: {}", partner_line));
+			}
+		}
+
+		summaries.push(PublishedEmbedSummary {
+			guild_id: sql_guild_id,
+			embed_id: embed.id,
+			summary: summary_lines.join("\n"),
+		});
+	}
+
+	Ok(summaries)
+}
+
+/// Builds the full set of messages for the given embed data, in sequence order. Each entry is a channel and the
+/// messages to publish in it, where each message is a group of embeds.
+pub fn build_channel_messages(
+	db_connection: &mut PgConnection,
+	guild: GuildId,
+	embed_data: Vec<EmbedData>,
+) -> miette::Result<Vec<(ChannelId, Vec<Vec<CreateEmbed>>)>> {
+	let default_channel_id: i64 = guild_settings::table
+		.find(guild.get() as i64)
+		.select(guild_settings::publish_channel)
+		.first(db_connection)
+		.into_diagnostic()?;
 
-	let mut embeds: Vec<CreateEmbed> = Vec::with_capacity(embed_data.len());
+	// Embeds are published to their channels in sequence order, so channels are kept in the order they first appear
+	let mut channel_embeds: Vec<(ChannelId, Vec<CreateEmbed>)> = Vec::new();
 	for embed in embed_data {
-		let mut new_embed = CreateEmbed::new();
-		if !embed.embed_text.is_empty() {
-			new_embed = new_embed.description(embed.embed_text);
+		let channel_id = ChannelId::new(embed.channel.unwrap_or(default_channel_id) as u64);
+		let new_embed = build_embed(embed, db_connection)?;
+		match channel_embeds.iter_mut().find(|(channel, _)| *channel == channel_id) {
+			Some((_, embeds)) => embeds.push(new_embed),
+			None => channel_embeds.push((channel_id, vec![new_embed])),
 		}
-		if !embed.image_url.is_empty() {
-			new_embed = new_embed.image(embed.image_url);
+	}
+
+	Ok(channel_embeds
+		.into_iter()
+		.map(|(channel_id, embeds)| (channel_id, group_embeds(embeds)))
+		.collect())
+}
+
+/// Groups embeds into the sets of embeds that will each make up a message, keeping each message within Discord's limits
+/// on the number of embeds and the total length of their text
+fn group_embeds(embeds: Vec<CreateEmbed>) -> Vec<Vec<CreateEmbed>> {
+	let mut embed_groups: Vec<Vec<CreateEmbed>> = vec![Vec::new()];
+	let mut group_length = 0;
+	for embed in embeds {
+		let embed_length = embed_text_length(&embed);
+		let last_group = embed_groups.last().unwrap();
+		if !last_group.is_empty()
+			&& (last_group.len() >= MAX_MESSAGE_EMBEDS || group_length + embed_length > MAX_EMBED_LENGTH)
+		{
+			embed_groups.push(Vec::new());
+			group_length = 0;
 		}
-		if let Some(color_number) = embed.color {
-			new_embed = new_embed.color(color_number);
+		group_length += embed_length;
+		embed_groups.last_mut().unwrap().push(embed);
+	}
+	embed_groups
+}
+
+/// Gets the length of the text in an embed that counts toward Discord's length limit
+fn embed_text_length(embed: &CreateEmbed) -> usize {
+	let value = serde_json::to_value(embed).unwrap_or_default();
+	let text_length = |pointer: &str| {
+		value
+			.pointer(pointer)
+			.and_then(|text| text.as_str())
+			.map_or(0, |text| text.chars().count())
+	};
+	let field_count = value
+		.get("fields")
+		.and_then(|fields| fields.as_array())
+		.map_or(0, |fields| fields.len());
+	let field_length: usize = (0..field_count)
+		.map(|index| text_length(&format!("/fields/{}/name", index)) + text_length(&format!("/fields/{}/value", index)))
+		.sum();
+	text_length("/title")
+		+ text_length("/description")
+		+ text_length("/author/name")
+		+ text_length("/footer/text")
+		+ field_length
+}
+
+/// Builds the Discord embed for a single set of embed data, including its partner list
+fn build_embed(embed: EmbedData, db_connection: &mut PgConnection) -> miette::Result<CreateEmbed> {
+	let partner_lines = match &embed.partner_category_list {
+		Some(partner_category) => {
+			let (category_name, partners) = category_partners(partner_category, db_connection)?;
+			render_partner_lines(&embed.partner_line_template, &category_name, &partners, true)
 		}
+		None => Vec::new(),
+	};
 
-		if let Some(partner_category) = embed.partner_category_list {
-			let partners: Vec<Partner> = partners::table
-				.filter(partners::category.eq(&partner_category))
-				.order(partners::display_name.asc())
-				.load(&mut *db_connection)
-				.into_diagnostic()?;
+	// Discord limits the total length of all text in an embed, so the partner list gets whatever the rest doesn't use
+	let used_length = [&embed.title, &embed.embed_text, &embed.author_name, &embed.footer_text]
+		.iter()
+		.map(|text| text.chars().count())
+		.sum::<usize>();
+	let field_budget = MAX_EMBED_LENGTH.saturating_sub(used_length);
 
-			if !partners.is_empty() {
-				// To prevent potential problems with field contents length, limit fields to 10 partners each
-				let mut field_lines: Vec<Vec<String>> = vec![Vec::new()];
-				for partner in partners.iter() {
-					let mut last_field = field_lines.last_mut().unwrap();
-					if last_field.len() >= 10 {
-						field_lines.push(Vec::new());
-						last_field = field_lines.last_mut().unwrap();
-					}
-					last_field.push(format!(
-						"- [{}](https://discord.gg/{})",
-						partner.display_name, partner.invite_code
-					));
+	let mut new_embed = CreateEmbed::new();
+	if !embed.title.is_empty() {
+		new_embed = new_embed.title(embed.title);
+		if !embed.title_url.is_empty() {
+			new_embed = new_embed.url(embed.title_url);
+		}
+	}
+	if !embed.embed_text.is_empty() {
+		new_embed = new_embed.description(embed.embed_text);
+	}
+	if !embed.image_url.is_empty() {
+		new_embed = new_embed.image(embed.image_url);
+	}
+	if !embed.thumbnail_url.is_empty() {
+		new_embed = new_embed.thumbnail(embed.thumbnail_url);
+	}
+	if !embed.author_name.is_empty() {
+		let mut author = CreateEmbedAuthor::new(embed.author_name);
+		if !embed.author_icon_url.is_empty() {
+			author = author.icon_url(embed.author_icon_url);
+		}
+		new_embed = new_embed.author(author);
+	}
+	if !embed.footer_text.is_empty() {
+		let mut footer = CreateEmbedFooter::new(embed.footer_text);
+		if !embed.footer_icon_url.is_empty() {
+			footer = footer.icon_url(embed.footer_icon_url);
+		}
+		new_embed = new_embed.footer(footer);
+	}
+	if embed.show_timestamp {
+		new_embed = new_embed.timestamp(Timestamp::now());
+	}
+	if let Some(color_number) = embed.color {
+		new_embed = new_embed.color(color_number);
+	}
+
+	if !partner_lines.is_empty() {
+		let field_contents = pack_fields(partner_lines, field_budget);
+		new_embed = new_embed.fields(field_contents.iter().map(|contents| ("", contents, true)));
+	}
+
+	Ok(new_embed)
+}
+
+/// Gets the name of a partner category and the listed partners in it, in listing order
+fn category_partners(
+	partner_category: &str,
+	db_connection: &mut PgConnection,
+) -> miette::Result<(String, Vec<Partner>)> {
+	let category_name: String = partner_categories::table
+		.find(partner_category)
+		.select(partner_categories::name)
+		.first(db_connection)
+		.into_diagnostic()?;
+	let partners: Vec<Partner> = partners::table
+		.filter(
+			partners::category
+				.eq(partner_category)
+				.and(partners::status.eq(PartnerStatus::Active.as_str())),
+		)
+		.order(partners::display_name.asc())
+		.load(db_connection)
+		.into_diagnostic()?;
+	Ok((category_name, partners))
+}
+
+/// Renders the list line for each partner using the embed's partner line template. Member counts are the ones
+/// recorded when each partner's invite was last resolved, so building embeds doesn't need to look up every invite. If
+/// member counts aren't shown, the member count placeholder is left as-is.
+fn render_partner_lines(
+	template: &str,
+	category_name: &str,
+	partners: &[Partner],
+	show_member_counts: bool,
+) -> Vec<String> {
+	partners
+		.iter()
+		.enumerate()
+		.map(|(index, partner)| {
+			let member_count = match (show_member_counts, partner.member_count) {
+				(false, _) => String::from("{member_count}"),
+				(true, Some(count)) => count.to_string(),
+				(true, None) => String::from("?"),
+			};
+			template
+				.replace("{number}", &(index + 1).to_string())
+				.replace("{emoji}", &partner.emoji)
+				.replace("{name}", &partner.display_name)
+				.replace("{invite}", &format!("https://discord.gg/{}", partner.invite_code))
+				.replace("{description}", &partner.description)
+				.replace("{category}", category_name)
+				.replace("{member_count}", &member_count)
+		})
+		.collect()
+}
+
+/// Packs partner lines into embed field contents, respecting Discord's limits on field length, field count, and
+/// total embed length. Lines that don't fit are summarized at the end of the list.
+fn pack_fields(lines: Vec<String>, length_budget: usize) -> Vec<String> {
+	let line_count = lines.len();
+	let mut fields: Vec<String> = Vec::new();
+	let mut used_length: usize = 0;
+	let mut packed_count: usize = 0;
+
+	for line in lines {
+		let line: String = if line.chars().count() > MAX_FIELD_LENGTH {
+			let mut truncated: String = line.chars().take(MAX_FIELD_LENGTH - 1).collect();
+			truncated.push('…');
+			truncated
+		} else {
+			line
+		};
+		let line_length = line.chars().count();
+
+		let fits_in_last_field = fields
+			.last()
+			.is_some_and(|field| field.chars().count() + 1 + line_length <= MAX_FIELD_LENGTH);
+		let added_length = if fits_in_last_field {
+			line_length + 1
+		} else {
+			line_length
+		};
+		// Leave room for the overflow line in case later lines don't fit
+		if used_length + added_length + OVERFLOW_LINE_RESERVE > length_budget {
+			break;
+		}
+		if fits_in_last_field {
+			let field = fields.last_mut().unwrap();
+			field.push('\n');
+			field.push_str(&line);
+		} else {
+			if fields.len() >= MAX_FIELDS {
+				break;
+			}
+			fields.push(line);
+		}
+		used_length += added_length;
+		packed_count += 1;
+	}
+
+	if packed_count < line_count {
+		let overflow_line = format!("…and {} more", line_count - packed_count);
+		let fits_in_last_field = fields
+			.last()
+			.is_some_and(|field| field.chars().count() + 1 + overflow_line.chars().count() <= MAX_FIELD_LENGTH);
+		if fits_in_last_field {
+			let field = fields.last_mut().unwrap();
+			field.push('\n');
+			field.push_str(&overflow_line);
+		} else if fields.len() < MAX_FIELDS {
+			fields.push(overflow_line);
+		}
+	}
+
+	fields
+}
+
+/// Publishes a set of embeds to a single channel, reusing the messages already published there
+async fn publish_to_channel(
+	ctx: &Context,
+	db_connection: &mut PgConnection,
+	guild: GuildId,
+	target: &PublishTarget,
+	publisher: &ChannelPublisher,
+	embed_groups: Vec<Vec<CreateEmbed>>,
+	existing_messages: Vec<PublishedMessage>,
+) -> miette::Result<()> {
+	let sql_guild_id = guild.get() as i64;
+
+	let mut new_messages: Vec<PublishedMessage> = Vec::new();
+	let mut existing_message_iter = existing_messages.into_iter();
+
+	for embed_group in embed_groups {
+		let content_hash = embed_group_hash(&embed_group);
+		match existing_message_iter.next() {
+			Some(message) => {
+				if message.content_hash == Some(content_hash) {
+					continue;
 				}
 
-				let field_contents: Vec<String> = field_lines.into_iter().map(|lines| lines.join("\n")).collect();
-				new_embed = new_embed.fields(field_contents.iter().map(|contents| ("", contents, true)));
+				let edit_result = publisher
+					.edit(
+						ctx,
+						target,
+						MessageId::new(message.message_id as u64),
+						embed_group.clone(),
+					)
+					.await;
+				let new_message_id = match edit_result {
+					Ok(_) => message.message_id,
+					Err(error) if is_not_found(&error) => {
+						// The message was deleted from Discord, so it needs to be sent again
+						let new_message_id = publisher.send(ctx, target, embed_group).await?;
+						new_message_id.get() as i64
+					}
+					Err(error) => return Err(error).into_diagnostic(),
+				};
+				diesel::update(published_messages::table)
+					.filter(
+						published_messages::guild_id
+							.eq(sql_guild_id)
+							.and(published_messages::message_id.eq(message.message_id)),
+					)
+					.set((
+						published_messages::message_id.eq(new_message_id),
+						published_messages::content_hash.eq(content_hash),
+					))
+					.execute(db_connection)
+					.into_diagnostic()?;
 			}
+			None => {
+				let new_message_id = publisher.send(ctx, target, embed_group).await?;
+				new_messages.push(PublishedMessage {
+					guild_id: sql_guild_id,
+					message_id: new_message_id.get() as i64,
+					channel_id: target.channel_id.get() as i64,
+					content_hash: Some(content_hash),
+					webhook_id: publisher.webhook_id(),
+					webhook_identity_hash: publisher.identity_hash(),
+					thread_id: target.thread_id.map(|thread_id| thread_id.get() as i64),
+				});
+			}
+		}
+	}
+
+	let unused_existing_messages: Vec<PublishedMessage> = existing_message_iter.collect();
+	if !unused_existing_messages.is_empty() {
+		for message in unused_existing_messages.iter() {
+			// Ignore permission errors
+			let _ = publisher
+				.delete(ctx, target, MessageId::new(message.message_id as u64))
+				.await;
 		}
-		embeds.push(new_embed);
+		let unused_existing_messages: Vec<i64> = unused_existing_messages
+			.iter()
+			.map(|message| message.message_id)
+			.collect();
+		diesel::delete(published_messages::table)
+			.filter(
+				published_messages::guild_id
+					.eq(sql_guild_id)
+					.and(published_messages::message_id.eq_any(&unused_existing_messages)),
+			)
+			.execute(db_connection)
+			.into_diagnostic()?;
+	}
+
+	if !new_messages.is_empty() {
+		diesel::insert_into(published_messages::table)
+			.values(new_messages)
+			.execute(db_connection)
+			.into_diagnostic()?;
 	}
 
-	if embeds.is_empty() {
-		if !existing_messages.is_empty() {
-			for message in existing_messages {
-				let message_id = message.message_id as u64;
+	Ok(())
+}
 
-				// Ignore permission errors
-				let _ = channel_id.delete_message(&ctx.http, message_id).await;
+/// Where a channel's part of the partner list is published
+struct PublishTarget {
+	channel_id: ChannelId,
+	/// The post the messages are in, for forum channels
+	thread_id: Option<ChannelId>,
+	/// Whether new messages are crossposted to the servers following the channel, for announcement channels
+	crosspost: bool,
+}
+
+impl PublishTarget {
+	/// The channel the messages themselves are in
+	fn message_channel(&self) -> ChannelId {
+		self.thread_id.unwrap_or(self.channel_id)
+	}
+}
+
+/// The way messages are sent to a channel the partner list is published in
+enum ChannelPublisher {
+	Bot,
+	Webhook {
+		webhook: Box<Webhook>,
+		username: String,
+		avatar_url: String,
+	},
+}
+
+impl ChannelPublisher {
+	/// The ID of the webhook messages are sent through, if any
+	fn webhook_id(&self) -> Option<i64> {
+		match self {
+			Self::Bot => None,
+			Self::Webhook { webhook, .. } => Some(webhook.id.get() as i64),
+		}
+	}
+
+	/// Hashes the name and avatar webhook messages are sent with, so changes to them can be detected
+	fn identity_hash(&self) -> Option<i64> {
+		match self {
+			Self::Bot => None,
+			Self::Webhook {
+				username, avatar_url, ..
+			} => {
+				// The separator keeps the boundary between the name and the avatar URL from being ambiguous
+				Some(stable_hash(format!("{}\0{}", username, avatar_url).as_bytes()))
 			}
-			diesel::delete(published_messages::table)
-				.filter(published_messages::guild_id.eq(sql_guild_id))
-				.execute(&mut *db_connection)
-				.into_diagnostic()?;
 		}
-	} else {
-		// Group embed into groups of 10
-		// For now, we're assuming that embeds won't exceed Discord's limit of 6000 characters across all embeds in a message
-		let mut embed_groups: Vec<Vec<CreateEmbed>> = vec![Vec::new()];
-		for embed in embeds {
-			let mut last_group = embed_groups.last_mut().unwrap();
-			if last_group.len() >= 10 {
-				embed_groups.push(Vec::new());
-				last_group = embed_groups.last_mut().unwrap();
-			}
-			last_group.push(embed);
-		}
-
-		let mut new_message_ids: Vec<MessageId> = Vec::new();
-		let mut existing_message_iter = existing_messages.into_iter();
-
-		for embed_group in embed_groups {
-			match existing_message_iter.next() {
-				Some(message) => {
-					let message_id = message.message_id as u64;
-					let message = EditMessage::new().embeds(embed_group);
-					channel_id
-						.edit_message(&ctx.http, message_id, message)
-						.await
-						.into_diagnostic()?;
+	}
+
+	/// Checks whether a published message was sent the way this publisher sends messages
+	fn sent(&self, message: &PublishedMessage) -> bool {
+		message.webhook_id == self.webhook_id() && message.webhook_identity_hash == self.identity_hash()
+	}
+
+	/// Sets up a message to send through the webhook with the configured name and avatar
+	fn webhook_message(username: &str, avatar_url: &str, embeds: Vec<CreateEmbed>) -> ExecuteWebhook {
+		let mut message = ExecuteWebhook::new().embeds(embeds);
+		if !username.is_empty() {
+			message = message.username(username);
+		}
+		if !avatar_url.is_empty() {
+			message = message.avatar_url(avatar_url);
+		}
+		message
+	}
+
+	async fn send(&self, ctx: &Context, target: &PublishTarget, embeds: Vec<CreateEmbed>) -> miette::Result<MessageId> {
+		let new_message_id = match self {
+			Self::Bot => {
+				let message = CreateMessage::new().embeds(embeds);
+				let new_message = target
+					.message_channel()
+					.send_message(&ctx.http, message)
+					.await
+					.into_diagnostic()?;
+				new_message.id
+			}
+			Self::Webhook {
+				webhook,
+				username,
+				avatar_url,
+			} => {
+				let mut message = Self::webhook_message(username, avatar_url, embeds);
+				if let Some(thread_id) = target.thread_id {
+					message = message.in_thread(thread_id);
 				}
-				None => {
-					let message = CreateMessage::new().embeds(embed_group);
-					let new_message = channel_id.send_message(&ctx.http, message).await.into_diagnostic()?;
-					new_message_ids.push(new_message.id);
+				let Some(new_message) = webhook.execute(&ctx.http, true, message).await.into_diagnostic()? else {
+					bail!("Discord didn't return the message sent through the publishing webhook");
+				};
+				new_message.id
+			}
+		};
+
+		if target.crosspost {
+			// Crossposts are heavily rate limited, and a message that isn't crossposted is still published
+			let _ = target.channel_id.crosspost(&ctx.http, new_message_id).await;
+		}
+
+		Ok(new_message_id)
+	}
+
+	/// Starts a forum post with the given embeds, returning the post and the ID of its first message
+	async fn create_forum_post(
+		&self,
+		ctx: &Context,
+		channel_id: ChannelId,
+		embeds: Vec<CreateEmbed>,
+	) -> miette::Result<(ChannelId, MessageId)> {
+		match self {
+			Self::Bot => {
+				let post = CreateForumPost::new(FORUM_POST_NAME, CreateMessage::new().embeds(embeds));
+				let thread = channel_id.create_forum_post(&ctx.http, post).await.into_diagnostic()?;
+
+				// The first message of a forum post shares its ID with the post
+				Ok((thread.id, MessageId::new(thread.id.get())))
+			}
+			Self::Webhook {
+				webhook,
+				username,
+				avatar_url,
+			} => {
+				let message =
+					Self::webhook_message(username, avatar_url, embeds).thread_name(FORUM_POST_NAME.to_string());
+				let Some(new_message) = webhook.execute(&ctx.http, true, message).await.into_diagnostic()? else {
+					bail!("Discord didn't return the message sent through the publishing webhook");
+				};
+				Ok((new_message.channel_id, new_message.id))
+			}
+		}
+	}
+
+	async fn edit(
+		&self,
+		ctx: &Context,
+		target: &PublishTarget,
+		message_id: MessageId,
+		embeds: Vec<CreateEmbed>,
+	) -> Result<(), SerenityError> {
+		match self {
+			Self::Bot => {
+				let message = EditMessage::new().embeds(embeds);
+				target
+					.message_channel()
+					.edit_message(&ctx.http, message_id, message)
+					.await?;
+			}
+			Self::Webhook { webhook, .. } => {
+				let mut message = EditWebhookMessage::new().embeds(embeds);
+				if let Some(thread_id) = target.thread_id {
+					message = message.in_thread(thread_id);
 				}
+				webhook.edit_message(&ctx.http, message_id, message).await?;
 			}
 		}
+		Ok(())
+	}
 
-		let unused_existing_messages: Vec<i64> = existing_message_iter.map(|message| message.message_id).collect();
-		if !unused_existing_messages.is_empty() {
-			diesel::delete(published_messages::table)
-				.filter(
-					published_messages::guild_id
-						.eq(sql_guild_id)
-						.and(published_messages::message_id.eq_any(&unused_existing_messages)),
-				)
-				.execute(&mut *db_connection)
-				.into_diagnostic()?;
+	async fn delete(&self, ctx: &Context, target: &PublishTarget, message_id: MessageId) -> Result<(), SerenityError> {
+		match self {
+			Self::Bot => target.message_channel().delete_message(&ctx.http, message_id).await,
+			Self::Webhook { webhook, .. } => webhook.delete_message(&ctx.http, target.thread_id, message_id).await,
 		}
+	}
+}
 
-		let new_messages: Vec<PublishedMessage> = new_message_ids
-			.into_iter()
-			.map(|message_id| PublishedMessage {
-				guild_id: sql_guild_id,
-				message_id: message_id.get() as i64,
-			})
-			.collect();
-		if !new_messages.is_empty() {
-			diesel::insert_into(published_messages::table)
-				.values(new_messages)
-				.execute(&mut *db_connection)
+/// Gets how messages should be sent to a publishing channel, creating the channel's webhook if the guild publishes
+/// through webhooks and the channel doesn't have one yet
+async fn channel_publisher(
+	ctx: &Context,
+	db_connection: &mut PgConnection,
+	settings: &GuildSettings,
+	channel_id: ChannelId,
+) -> miette::Result<ChannelPublisher> {
+	if !settings.webhook_publishing {
+		return Ok(ChannelPublisher::Bot);
+	}
+
+	let sql_channel_id = channel_id.get() as i64;
+	let existing_webhook: Option<PublishWebhook> = publish_webhooks::table
+		.find(sql_channel_id)
+		.first(db_connection)
+		.optional()
+		.into_diagnostic()?;
+	let existing_webhook = match existing_webhook {
+		Some(existing_webhook) => match Webhook::from_url(&ctx.http, &existing_webhook.webhook_url).await {
+			Ok(webhook) => Some(webhook),
+			// Someone deleted the webhook, so a new one needs to be made
+			Err(error) if is_not_found(&error) => None,
+			Err(error) => return Err(error).into_diagnostic(),
+		},
+		None => None,
+	};
+	let webhook = match existing_webhook {
+		Some(webhook) => webhook,
+		None => {
+			let webhook = channel_id
+				.create_webhook(&ctx.http, CreateWebhook::new(PUBLISH_WEBHOOK_NAME))
+				.await
+				.into_diagnostic()?;
+			let webhook_url = webhook.url().into_diagnostic()?;
+			let new_webhook = PublishWebhook {
+				channel_id: sql_channel_id,
+				guild_id: settings.guild_id,
+				webhook_id: webhook.id.get() as i64,
+				webhook_url: webhook_url.clone(),
+			};
+			diesel::insert_into(publish_webhooks::table)
+				.values(new_webhook)
+				.on_conflict(publish_webhooks::channel_id)
+				.do_update()
+				.set((
+					publish_webhooks::webhook_id.eq(webhook.id.get() as i64),
+					publish_webhooks::webhook_url.eq(webhook_url),
+				))
+				.execute(db_connection)
 				.into_diagnostic()?;
+			webhook
 		}
+	};
+
+	Ok(ChannelPublisher::Webhook {
+		webhook: Box::new(webhook),
+		username: settings.webhook_username.clone(),
+		avatar_url: settings.webhook_avatar_url.clone(),
+	})
+}
+
+/// Checks that the forum post the partner list was published in still exists, reopening it if it was archived.
+/// Returns the post if it's still there.
+async fn open_forum_post(ctx: &Context, thread_id: Option<i64>) -> miette::Result<Option<ChannelId>> {
+	let Some(thread_id) = thread_id else {
+		return Ok(None);
+	};
+	let thread_id = ChannelId::new(thread_id as u64);
+	let thread = match thread_id.to_channel(&ctx.http).await {
+		Ok(thread) => thread,
+		Err(error) if is_not_found(&error) => return Ok(None),
+		Err(error) => return Err(error).into_diagnostic(),
+	};
+
+	// Messages in archived posts can't be edited
+	let archived = thread
+		.guild()
+		.and_then(|thread| thread.thread_metadata)
+		.is_some_and(|metadata| metadata.archived);
+	if archived {
+		thread_id
+			.edit_thread(&ctx.http, EditThread::new().archived(false))
+			.await
+			.into_diagnostic()?;
 	}
 
-	Ok(())
+	Ok(Some(thread_id))
+}
+
+/// Deletes published messages from Discord. Forum posts the messages are in are deleted whole when possible.
+/// Returns the IDs of the messages that couldn't be deleted along with why; messages that were already gone count as
+/// deleted.
+pub async fn delete_published_messages(
+	ctx: &Context,
+	webhooks: &[PublishWebhook],
+	messages: &[PublishedMessage],
+) -> Vec<(i64, SerenityError)> {
+	let mut deleted_threads: Vec<i64> = Vec::new();
+	let mut undeletable_threads: Vec<i64> = Vec::new();
+	let mut failures: Vec<(i64, SerenityError)> = Vec::new();
+
+	for message in messages {
+		if let Some(thread_id) = message.thread_id {
+			if deleted_threads.contains(&thread_id) {
+				continue;
+			}
+			if !undeletable_threads.contains(&thread_id) {
+				match ChannelId::new(thread_id as u64).delete(&ctx.http).await {
+					Ok(_) => {
+						deleted_threads.push(thread_id);
+						continue;
+					}
+					Err(error) if is_not_found(&error) => {
+						deleted_threads.push(thread_id);
+						continue;
+					}
+					// Without permission to delete the post, its messages can still be deleted one by one
+					Err(_) => undeletable_threads.push(thread_id),
+				}
+			}
+		}
+
+		match delete_published_message(ctx, webhooks, message).await {
+			Ok(_) => (),
+			Err(error) if is_not_found(&error) => (),
+			Err(error) => failures.push((message.message_id, error)),
+		}
+	}
+
+	failures
+}
+
+/// Deletes a published message, going through the webhook that sent it if there was one
+async fn delete_published_message(
+	ctx: &Context,
+	webhooks: &[PublishWebhook],
+	message: &PublishedMessage,
+) -> Result<(), SerenityError> {
+	let thread_id = message.thread_id.map(|thread_id| ChannelId::new(thread_id as u64));
+	let channel_id = thread_id.unwrap_or(ChannelId::new(message.channel_id as u64));
+	let message_id = MessageId::new(message.message_id as u64);
+	let sending_webhook = webhooks
+		.iter()
+		.find(|webhook| Some(webhook.webhook_id) == message.webhook_id);
+	if let Some(sending_webhook) = sending_webhook {
+		let webhook_delete_result = match Webhook::from_url(&ctx.http, &sending_webhook.webhook_url).await {
+			Ok(webhook) => webhook.delete_message(&ctx.http, thread_id, message_id).await,
+			Err(error) => Err(error),
+		};
+		if webhook_delete_result.is_ok() {
+			return Ok(());
+		}
+	}
+
+	// Messages from webhooks the bot no longer has can still be deleted if the bot can manage messages
+	channel_id.delete_message(&ctx.http, message_id).await
+}
+
+/// Hashes the contents of a message's embeds so unchanged messages don't need to be edited. Embeds are hashed as
+/// the JSON sent to Discord, leaving out their timestamps, which are set to the current time on every build and would
+/// otherwise cause every message to be edited on every publish.
+fn embed_group_hash(embed_group: &[CreateEmbed]) -> i64 {
+	let embed_values: Vec<serde_json::Value> = embed_group
+		.iter()
+		.map(|embed| {
+			let mut value = serde_json::to_value(embed).unwrap_or_default();
+			if let Some(fields) = value.as_object_mut() {
+				fields.remove("timestamp");
+			}
+			value
+		})
+		.collect();
+	// JSON objects are serialized with their keys in sorted order, so the same embeds always give the same text
+	let embed_json = serde_json::to_string(&embed_values).unwrap_or_default();
+	stable_hash(embed_json.as_bytes())
+}
+
+/// Hashes data with 64-bit FNV-1a. Hashes are stored in the database, so unlike the standard library's hasher, the
+/// result must stay the same across Rust versions.
+fn stable_hash(data: &[u8]) -> i64 {
+	const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+	const FNV_PRIME: u64 = 0x100000001b3;
+	let hash = data.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+		(hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+	});
+	hash as i64
+}
+
+/// Checks whether a Discord request failed because the target (such as a message) doesn't exist
+pub fn is_not_found(error: &SerenityError) -> bool {
+	matches!(
+		error,
+		SerenityError::Http(HttpError::UnsuccessfulRequest(ErrorResponse {
+			status_code: StatusCode::NOT_FOUND,
+			..
+		}))
+	)
 }
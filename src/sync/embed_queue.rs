@@ -0,0 +1,257 @@
+use crate::database::get_database_connection;
+use crate::sync::embed::{
+	forget_published_messages, move_default_channel, publish_changes, remove_embed, republish_deleted_messages,
+	update_embed,
+};
+use crate::sync::retention::delete_guild_data;
+use miette::{bail, IntoDiagnostic};
+use serenity::client::Context;
+use serenity::model::id::{ChannelId, GuildId, MessageId};
+use serenity::prelude::{SerenityError, TypeMapKey};
+use std::time::Duration;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
+use tokio::sync::oneshot;
+use tokio::time::{timeout_at, Instant};
+
+/// How long to wait for more changes to a guild before updating its embeds
+const UPDATE_DEBOUNCE_TIME: Duration = Duration::from_secs(2);
+
+/// The longest updates are held back while changes keep coming in
+const MAX_UPDATE_DELAY: Duration = Duration::from_secs(10);
+
+/// A request for the update worker to do something with a guild's embeds
+pub struct EmbedUpdate {
+	guild: GuildId,
+	job: EmbedJob,
+}
+
+/// The work the update worker can do for a guild
+enum EmbedJob {
+	/// Publishes changes to the guild's embeds
+	Update {
+		/// Whether to publish even if the guild is in draft mode
+		force: bool,
+		/// Notified with whether publishing succeeded once the update is done
+		done: Vec<oneshot::Sender<bool>>,
+	},
+	/// Republishes the guild's embeds if any of the deleted messages were published embed messages
+	RepublishDeleted { message_ids: Vec<MessageId> },
+	/// Stops tracking published messages that no longer exist
+	ForgetMessages { message_ids: Vec<MessageId> },
+	/// Removes all of the guild's published messages
+	Remove { done: oneshot::Sender<miette::Result<()>> },
+	/// Moves the partner list to a new default publish channel
+	MoveDefaultChannel {
+		channel: ChannelId,
+		done: oneshot::Sender<miette::Result<Vec<SerenityError>>>,
+	},
+	/// Deletes everything stored for the guild
+	DeleteGuildData { done: oneshot::Sender<miette::Result<()>> },
+}
+
+impl EmbedUpdate {
+	/// Adds the request to the batch. Consecutive updates for a guild are combined; other jobs are kept in the order
+	/// they were queued, so an update queued after a removal or move still happens after it.
+	fn add_to(self, batch: &mut Vec<EmbedUpdate>) {
+		let guild = self.guild;
+		let last_guild_job = batch.iter_mut().rev().find(|batched| batched.guild == guild);
+		let job = match (last_guild_job, self.job) {
+			(
+				Some(EmbedUpdate {
+					job: EmbedJob::Update {
+						force: batched_force,
+						done: batched_done,
+					},
+					..
+				}),
+				EmbedJob::Update { force, done },
+			) => {
+				*batched_force |= force;
+				batched_done.extend(done);
+				return;
+			}
+			(_, job) => job,
+		};
+		batch.push(EmbedUpdate { guild, job });
+	}
+
+	/// Whether someone is waiting on the job, so the batch shouldn't wait for more changes
+	fn is_awaited(&self) -> bool {
+		matches!(
+			self.job,
+			EmbedJob::Remove { .. } | EmbedJob::MoveDefaultChannel { .. } | EmbedJob::DeleteGuildData { .. }
+		)
+	}
+}
+
+pub struct EmbedUpdateQueue;
+
+impl TypeMapKey for EmbedUpdateQueue {
+	type Value = UnboundedSender<EmbedUpdate>;
+}
+
+/// The receiving end of the embed update queue, held until the update worker is started
+pub struct EmbedUpdateReceiver;
+
+impl TypeMapKey for EmbedUpdateReceiver {
+	type Value = UnboundedReceiver<EmbedUpdate>;
+}
+
+/// Queues an update of the guild's embeds. The update happens in the background, so this doesn't wait on Discord.
+pub async fn queue_embed_update(ctx: &Context, guild: GuildId) {
+	let job = EmbedJob::Update {
+		force: false,
+		done: Vec::new(),
+	};
+	send_update(ctx, EmbedUpdate { guild, job }).await;
+}
+
+/// Queues a publish of the guild's embeds that happens even if the guild is in draft mode. The returned receiver gets
+/// whether publishing succeeded; failures are also reported in the guild's staff log.
+pub async fn queue_embed_publish(ctx: &Context, guild: GuildId) -> oneshot::Receiver<bool> {
+	let (done_sender, done_receiver) = oneshot::channel();
+	let job = EmbedJob::Update {
+		force: true,
+		done: vec![done_sender],
+	};
+	send_update(ctx, EmbedUpdate { guild, job }).await;
+	done_receiver
+}
+
+/// Queues a republish of the guild's embeds in case any of the deleted messages were published embed messages
+pub async fn queue_deleted_message_republish(ctx: &Context, guild: GuildId, message_ids: Vec<MessageId>) {
+	let job = EmbedJob::RepublishDeleted { message_ids };
+	send_update(ctx, EmbedUpdate { guild, job }).await;
+}
+
+/// Queues removing the rows for published messages that no longer exist
+pub async fn queue_forget_published_messages(ctx: &Context, guild: GuildId, message_ids: Vec<MessageId>) {
+	let job = EmbedJob::ForgetMessages { message_ids };
+	send_update(ctx, EmbedUpdate { guild, job }).await;
+}
+
+/// Removes all of the guild's published messages through the update worker, so the removal can't overlap with a
+/// publish. Waits for the removal to finish.
+pub async fn remove_published_embeds(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+	let (done_sender, done_receiver) = oneshot::channel();
+	let job = EmbedJob::Remove { done: done_sender };
+	send_update(ctx, EmbedUpdate { guild, job }).await;
+	match done_receiver.await {
+		Ok(result) => result,
+		Err(_) => bail!("The embed update worker stopped before removing the published embeds"),
+	}
+}
+
+/// Moves the partner list to a new default publish channel through the update worker, so the move can't overlap with
+/// a publish. Waits for the move to finish, returning the errors for any messages that couldn't be deleted; if there
+/// are any, the channel wasn't changed.
+pub async fn move_default_publish_channel(
+	ctx: &Context,
+	guild: GuildId,
+	channel: ChannelId,
+) -> miette::Result<Vec<SerenityError>> {
+	let (done_sender, done_receiver) = oneshot::channel();
+	let job = EmbedJob::MoveDefaultChannel {
+		channel,
+		done: done_sender,
+	};
+	send_update(ctx, EmbedUpdate { guild, job }).await;
+	match done_receiver.await {
+		Ok(result) => result,
+		Err(_) => bail!("The embed update worker stopped before moving the publish channel"),
+	}
+}
+
+/// Deletes everything stored for the guild through the update worker, so a publish that's still queued can't write
+/// published messages back afterward. Waits for the deletion to finish.
+pub async fn purge_guild_data(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+	let (done_sender, done_receiver) = oneshot::channel();
+	let job = EmbedJob::DeleteGuildData { done: done_sender };
+	send_update(ctx, EmbedUpdate { guild, job }).await;
+	match done_receiver.await {
+		Ok(result) => result,
+		Err(_) => bail!("The embed update worker stopped before deleting the guild's data"),
+	}
+}
+
+async fn send_update(ctx: &Context, update: EmbedUpdate) {
+	let data = ctx.data.read().await;
+	let queue = data.get::<EmbedUpdateQueue>().unwrap();
+
+	// Sending only fails once the worker has stopped, which only happens when the bot is shutting down
+	let _ = queue.send(update);
+}
+
+/// Updates the embeds for guilds as they're queued. Updates that come in close together are combined so a burst of
+/// changes publishes once, and jobs are run one at a time so they never overlap. This is the only place published
+/// messages are changed from, so nothing else may call the functions it runs.
+pub async fn embed_update_worker(ctx: &Context, mut receiver: UnboundedReceiver<EmbedUpdate>) {
+	while let Some(update) = receiver.recv().await {
+		let mut batch: Vec<EmbedUpdate> = Vec::new();
+		let mut next_update = Some(update);
+		let latest_update_time = Instant::now() + MAX_UPDATE_DELAY;
+		while let Some(update) = next_update.take() {
+			update.add_to(&mut batch);
+
+			// Someone is waiting on removals, moves, and deletions, so they're run right away
+			if batch.iter().any(EmbedUpdate::is_awaited) {
+				break;
+			}
+			let wait_until = (Instant::now() + UPDATE_DEBOUNCE_TIME).min(latest_update_time);
+			if let Ok(update) = timeout_at(wait_until, receiver.recv()).await {
+				next_update = update;
+			}
+		}
+
+		for EmbedUpdate { guild, job } in batch {
+			run_job(ctx, guild, job).await;
+		}
+	}
+}
+
+async fn run_job(ctx: &Context, guild: GuildId, job: EmbedJob) {
+	match job {
+		EmbedJob::Update { force, done } => {
+			let update_result = if force {
+				publish_changes(ctx, guild).await
+			} else {
+				update_embed(ctx, guild).await
+			};
+			// Publishing failures are also reported in the guild's staff log
+			if let Err(error) = &update_result {
+				eprintln!("Failed to update embeds for guild {}: {:?}", guild, error);
+			}
+			for done in done {
+				let _ = done.send(update_result.is_ok());
+			}
+		}
+		EmbedJob::RepublishDeleted { message_ids } => {
+			if let Err(error) = republish_deleted_messages(ctx, guild, &message_ids).await {
+				eprintln!(
+					"Failed to republish deleted embed messages for guild {}: {:?}",
+					guild, error
+				);
+			}
+		}
+		EmbedJob::ForgetMessages { message_ids } => {
+			if let Err(error) = forget_published_messages(ctx, guild, &message_ids).await {
+				eprintln!(
+					"Failed to remove orphaned embed messages for guild {}: {:?}",
+					guild, error
+				);
+			}
+		}
+		EmbedJob::Remove { done } => {
+			let _ = done.send(remove_embed(ctx, guild).await);
+		}
+		EmbedJob::MoveDefaultChannel { channel, done } => {
+			let _ = done.send(move_default_channel(ctx, guild, channel).await);
+		}
+		EmbedJob::DeleteGuildData { done } => {
+			let db_connection = get_database_connection(ctx).await;
+			let mut db_connection = db_connection.lock().await;
+			let delete_result = delete_guild_data(&mut db_connection, guild.get() as i64).into_diagnostic();
+			let _ = done.send(delete_result);
+		}
+	}
+}
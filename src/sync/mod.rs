@@ -1,2 +1,7 @@
+pub mod announcement;
 pub mod embed;
+pub mod embed_queue;
+pub mod partner_names;
+pub mod reconcile;
+pub mod retention;
 pub mod role;
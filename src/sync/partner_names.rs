@@ -0,0 +1,241 @@
+use crate::database::get_database_connection;
+use crate::models::Partner;
+use crate::schema::{guild_settings, partners};
+use crate::sync::embed_queue::queue_embed_update;
+use crate::utils::staff_log::{log_embed, send_staff_log, send_staff_log_with_components, LogKind};
+use diesel::prelude::*;
+use diesel::result::{DatabaseErrorKind, Error as DbError};
+use miette::{bail, IntoDiagnostic};
+use serenity::builder::{
+	CreateActionRow, CreateAllowedMentions, CreateButton, CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use serenity::client::Context;
+use serenity::model::application::{ButtonStyle, ComponentInteraction};
+use serenity::model::id::GuildId;
+use serenity::model::invite::Invite;
+use serenity::model::permissions::Permissions;
+use std::time::Duration;
+use tokio::time::{interval, sleep};
+
+/// How often partner invites are resolved to check for renamed servers and refresh member counts
+const NAME_CHECK_INTERVAL: Duration = Duration::from_secs(43200);
+
+/// How long to wait between invite lookups, to stay well clear of Discord's rate limits
+const INVITE_CHECK_DELAY: Duration = Duration::from_secs(1);
+
+/// The custom ID prefix for the buttons on rename suggestions; the partnership ID follows it
+pub const APPLY_NAME_BUTTON_PREFIX: &str = "apply_partner_name:";
+
+/// Periodically resolves every partner's invite to detect partner servers that were renamed, suggesting the new name
+/// to staff for partners that still use the server name as their display name. The member counts shown in partner
+/// lists are refreshed along the way.
+pub async fn check_partner_names_task(ctx: &Context) -> miette::Result<()> {
+	let mut interval = interval(NAME_CHECK_INTERVAL);
+	let db_connection = get_database_connection(ctx).await;
+
+	loop {
+		interval.tick().await;
+
+		let guild_ids: Vec<i64> = {
+			let mut db_connection = db_connection.lock().await;
+			guild_settings::table
+				.filter(guild_settings::departed_at.is_null())
+				.select(guild_settings::guild_id)
+				.load(&mut *db_connection)
+				.into_diagnostic()?
+		};
+
+		// Failures for one guild or partner are logged so they don't stop the rest from being checked
+		for guild_id in guild_ids {
+			let guild = GuildId::new(guild_id as u64);
+			let guild_partners: QueryResult<Vec<Partner>> = {
+				let mut db_connection = db_connection.lock().await;
+				partners::table
+					.filter(partners::guild.eq(guild_id))
+					.load(&mut *db_connection)
+			};
+			let guild_partners = match guild_partners {
+				Ok(partners) => partners,
+				Err(error) => {
+					eprintln!("Failed to load partners to check for guild {}: {:?}", guild, error);
+					continue;
+				}
+			};
+			let mut member_counts_changed = false;
+			for partner in guild_partners {
+				sleep(INVITE_CHECK_DELAY).await;
+				// Invites that no longer resolve are a separate problem, and there's nothing new to find
+				let Ok(invite) = Invite::get(ctx, &partner.invite_code, true, false, None).await else {
+					continue;
+				};
+				match record_member_count(ctx, &partner, &invite).await {
+					Ok(changed) => member_counts_changed |= changed,
+					Err(error) => eprintln!(
+						"Failed to record member count for partner {}: {:?}",
+						partner.partnership_id, error
+					),
+				}
+				if let Err(error) = check_partner_name(ctx, guild, &partner, invite).await {
+					eprintln!(
+						"Failed to check server name for partner {}: {:?}",
+						partner.partnership_id, error
+					);
+				}
+			}
+			if member_counts_changed {
+				queue_embed_update(ctx, guild).await;
+			}
+		}
+	}
+}
+
+/// Records the member count of a partner's server from its resolved invite. Returns whether the count changed.
+async fn record_member_count(ctx: &Context, partner: &Partner, invite: &Invite) -> miette::Result<bool> {
+	let member_count = invite.approximate_member_count.map(|count| count as i32);
+	if member_count.is_none() || member_count == partner.member_count {
+		return Ok(false);
+	}
+
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+	diesel::update(partners::table)
+		.filter(partners::partnership_id.eq(&partner.partnership_id))
+		.set(partners::member_count.eq(member_count))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+	Ok(true)
+}
+
+/// Checks whether a partner's server was renamed, recording the new name and suggesting it to staff if the partner
+/// doesn't have a custom display name
+async fn check_partner_name(ctx: &Context, guild: GuildId, partner: &Partner, invite: Invite) -> miette::Result<()> {
+	let Some(invite_guild) = invite.guild else {
+		return Ok(());
+	};
+	if invite_guild.id.get() as i64 != partner.partner_guild || invite_guild.name == partner.partner_guild_name {
+		return Ok(());
+	}
+
+	{
+		let db_connection = get_database_connection(ctx).await;
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(partners::table)
+			.filter(partners::partnership_id.eq(&partner.partnership_id))
+			.set(partners::partner_guild_name.eq(&invite_guild.name))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?;
+	}
+
+	// Partners added before server names were recorded have nothing to compare against yet. Partners whose display
+	// name differs from the old server name were given a custom name, which shouldn't be replaced.
+	if partner.partner_guild_name.is_empty()
+		|| partner.display_name != partner.partner_guild_name
+		|| partner.display_name == invite_guild.name
+	{
+		return Ok(());
+	}
+
+	let embed = log_embed(LogKind::Changed, "Partner server renamed", None)
+		.field("Partner", &partner.display_name, true)
+		.field("New server name", &invite_guild.name, true);
+	let apply_button = CreateButton::new(format!("{}{}", APPLY_NAME_BUTTON_PREFIX, partner.partnership_id))
+		.label("Apply new name")
+		.style(ButtonStyle::Primary);
+	let components = vec![CreateActionRow::Buttons(vec![apply_button])];
+	send_staff_log_with_components(ctx, guild, embed, components).await
+}
+
+/// Handles the "Apply new name" button on a rename suggestion, setting the partner's display name to its server's
+/// current name
+pub async fn apply_suggested_name(ctx: &Context, component: &ComponentInteraction) -> miette::Result<()> {
+	let Some(guild) = component.guild_id else {
+		bail!("Rename suggestion button was used outside of a guild");
+	};
+	let Some(partnership_id) = component.data.custom_id.strip_prefix(APPLY_NAME_BUTTON_PREFIX) else {
+		bail!(
+			"Unexpected custom ID for rename suggestion button: {}",
+			component.data.custom_id
+		);
+	};
+
+	let can_manage = component
+		.member
+		.as_ref()
+		.and_then(|member| member.permissions)
+		.is_some_and(|permissions| permissions.contains(Permissions::MANAGE_GUILD));
+	if !can_manage {
+		let message = CreateInteractionResponseMessage::new()
+			.ephemeral(true)
+			.content("You need the Manage Server permission to rename partners.");
+		component
+			.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+			.await
+			.into_diagnostic()?;
+		return Ok(());
+	}
+
+	let sql_guild_id = guild.get() as i64;
+	let db_connection = get_database_connection(ctx).await;
+	let (partner, update_result) = {
+		let mut db_connection = db_connection.lock().await;
+		let partner: Option<Partner> = partners::table
+			.filter(
+				partners::partnership_id
+					.eq(partnership_id)
+					.and(partners::guild.eq(sql_guild_id)),
+			)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?;
+		let Some(partner) = partner else {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content("That server is no longer a partner.");
+			component
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		};
+		let update_result: QueryResult<usize> = diesel::update(partners::table)
+			.filter(partners::partnership_id.eq(partnership_id))
+			.set(partners::display_name.eq(&partner.partner_guild_name))
+			.execute(&mut *db_connection);
+		(partner, update_result)
+	};
+
+	match update_result {
+		Ok(_) => (),
+		Err(DbError::DatabaseError(DatabaseErrorKind::UniqueViolation, _)) => {
+			let message = CreateInteractionResponseMessage::new()
+				.ephemeral(true)
+				.content(format!(
+					"You already have another partner named {}.",
+					partner.partner_guild_name
+				))
+				.allowed_mentions(CreateAllowedMentions::new());
+			component
+				.create_response(&ctx.http, CreateInteractionResponse::Message(message))
+				.await
+				.into_diagnostic()?;
+			return Ok(());
+		}
+		Err(error) => bail!(error),
+	}
+
+	// Remove the button so the suggestion can't be applied twice
+	let message = CreateInteractionResponseMessage::new().components(Vec::new());
+	component
+		.create_response(&ctx.http, CreateInteractionResponse::UpdateMessage(message))
+		.await
+		.into_diagnostic()?;
+
+	let embed = log_embed(LogKind::Changed, "Partner renamed", Some(component.user.id))
+		.field("Old name", &partner.display_name, true)
+		.field("New name", &partner.partner_guild_name, true);
+	send_staff_log(ctx, guild, embed).await?;
+
+	queue_embed_update(ctx, guild).await;
+
+	Ok(())
+}
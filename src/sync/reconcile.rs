@@ -0,0 +1,92 @@
+use crate::database::get_database_connection;
+use crate::models::{GuildSettings, PublishedMessage};
+use crate::schema::{guild_settings, published_messages};
+use crate::sync::embed::is_not_found;
+use crate::sync::embed_queue::{queue_embed_update, queue_forget_published_messages};
+use diesel::prelude::*;
+use miette::IntoDiagnostic;
+use serenity::client::Context;
+use serenity::model::id::{ChannelId, GuildId, MessageId};
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// How long to wait between checks of published messages, to stay well clear of Discord's rate limits
+const MESSAGE_CHECK_DELAY: Duration = Duration::from_millis(500);
+
+/// Counts of what was found while reconciling published messages
+#[derive(Default)]
+struct ReconcileSummary {
+	guilds: usize,
+	checked_messages: usize,
+	orphaned_messages: usize,
+	unchecked_messages: usize,
+	republished_guilds: usize,
+}
+
+/// Checks that every tracked published message still exists, removing the rows for messages that don't and
+/// republishing the embeds for guilds that lost messages
+pub async fn reconcile_all_embeds_task(ctx: &Context) -> miette::Result<()> {
+	let db_connection = get_database_connection(ctx).await;
+	let all_guild_settings: Vec<GuildSettings> = {
+		let mut db_connection = db_connection.lock().await;
+		guild_settings::table
+			.filter(guild_settings::departed_at.is_null())
+			.load(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	let mut summary = ReconcileSummary::default();
+	for guild_data in all_guild_settings {
+		summary.guilds += 1;
+		let guild = GuildId::new(guild_data.guild_id as u64);
+
+		let messages: Vec<PublishedMessage> = {
+			let mut db_connection = db_connection.lock().await;
+			published_messages::table
+				.filter(published_messages::guild_id.eq(guild_data.guild_id))
+				.load(&mut *db_connection)
+				.into_diagnostic()?
+		};
+
+		let mut orphaned_message_ids: Vec<MessageId> = Vec::new();
+		for message in messages {
+			sleep(MESSAGE_CHECK_DELAY).await;
+			let channel = ChannelId::new(message.thread_id.unwrap_or(message.channel_id) as u64);
+			let message_id = MessageId::new(message.message_id as u64);
+			match channel.message(&ctx.http, message_id).await {
+				Ok(_) => summary.checked_messages += 1,
+				Err(error) if is_not_found(&error) => {
+					summary.checked_messages += 1;
+					orphaned_message_ids.push(message_id);
+				}
+				// Missing permissions and similar problems don't tell us whether the message is still there
+				Err(_) => summary.unchecked_messages += 1,
+			}
+		}
+
+		if orphaned_message_ids.is_empty() {
+			continue;
+		}
+		summary.orphaned_messages += orphaned_message_ids.len();
+		queue_forget_published_messages(ctx, guild, orphaned_message_ids).await;
+
+		// Guilds that opted out of republishing deleted messages or are holding drafts get their embeds back on the
+		// next publish instead
+		if !guild_data.republish_deleted_messages || guild_data.draft_mode {
+			continue;
+		}
+		queue_embed_update(ctx, guild).await;
+		summary.republished_guilds += 1;
+	}
+
+	println!(
+		"Reconciled published embeds for {} guilds: {} messages checked, {} orphaned messages removed, {} messages couldn't be checked, {} guilds queued for republishing",
+		summary.guilds,
+		summary.checked_messages,
+		summary.orphaned_messages,
+		summary.unchecked_messages,
+		summary.republished_guilds
+	);
+
+	Ok(())
+}
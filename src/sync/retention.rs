@@ -0,0 +1,146 @@
+use crate::database::get_database_connection;
+use crate::schema::{
+	archived_partners, embed_data, guild_settings, partner_categories, partner_role_exempt_roles,
+	partner_role_exempt_users, partners, partnership_requests, publish_webhooks, published_embed_summaries,
+	published_messages,
+};
+use crate::sync::embed_queue::{purge_guild_data, queue_embed_update};
+use diesel::prelude::*;
+use miette::IntoDiagnostic;
+use serenity::client::Context;
+use serenity::model::id::GuildId;
+use std::time::{Duration, SystemTime};
+use tokio::time::interval;
+
+/// How often to check for departed guilds whose data should be purged
+const PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Marks a guild the bot was removed from as departed. Its data is kept until the retention period passes in case the
+/// bot is added back.
+pub async fn mark_guild_departed(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+	diesel::update(guild_settings::table)
+		.filter(
+			guild_settings::guild_id
+				.eq(guild.get() as i64)
+				.and(guild_settings::departed_at.is_null()),
+		)
+		.set(guild_settings::departed_at.eq(SystemTime::now()))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+	Ok(())
+}
+
+/// Marks a guild as present, restoring its data if the bot had been removed from it
+pub async fn mark_guild_present(ctx: &Context, guild: GuildId) -> miette::Result<()> {
+	let db_connection = get_database_connection(ctx).await;
+	let restored_count = {
+		let mut db_connection = db_connection.lock().await;
+		diesel::update(guild_settings::table)
+			.filter(
+				guild_settings::guild_id
+					.eq(guild.get() as i64)
+					.and(guild_settings::departed_at.is_not_null()),
+			)
+			.set(guild_settings::departed_at.eq(None::<SystemTime>))
+			.execute(&mut *db_connection)
+			.into_diagnostic()?
+	};
+
+	// The partner list may have been changed or deleted while the bot was gone
+	if restored_count > 0 {
+		queue_embed_update(ctx, guild).await;
+	}
+
+	Ok(())
+}
+
+/// Marks guilds the bot was removed from while it was offline as departed
+pub async fn mark_missing_guilds_departed(ctx: &Context, present_guilds: &[GuildId]) -> miette::Result<()> {
+	let present_guild_ids: Vec<i64> = present_guilds.iter().map(|guild| guild.get() as i64).collect();
+	let db_connection = get_database_connection(ctx).await;
+	let mut db_connection = db_connection.lock().await;
+	diesel::update(guild_settings::table)
+		.filter(
+			guild_settings::guild_id
+				.ne_all(present_guild_ids)
+				.and(guild_settings::departed_at.is_null()),
+		)
+		.set(guild_settings::departed_at.eq(SystemTime::now()))
+		.execute(&mut *db_connection)
+		.into_diagnostic()?;
+	Ok(())
+}
+
+/// Periodically deletes all data for guilds the bot was removed from longer than the retention period ago
+pub async fn purge_departed_guilds_task(ctx: &Context, retention: Duration) -> miette::Result<()> {
+	let mut interval = interval(PURGE_INTERVAL);
+	let db_connection = get_database_connection(ctx).await;
+
+	loop {
+		interval.tick().await;
+
+		let Some(purge_before) = SystemTime::now().checked_sub(retention) else {
+			continue;
+		};
+		let expired_guilds: Vec<i64> = {
+			let mut db_connection = db_connection.lock().await;
+			guild_settings::table
+				.filter(guild_settings::departed_at.lt(purge_before))
+				.select(guild_settings::guild_id)
+				.load(&mut *db_connection)
+				.into_diagnostic()?
+		};
+		for guild_id in expired_guilds {
+			purge_guild_data(ctx, GuildId::new(guild_id as u64)).await?;
+		}
+	}
+}
+
+/// Deletes everything stored for a guild in a single transaction. Only the update worker may call this, through
+/// [`purge_guild_data`].
+pub fn delete_guild_data(db_connection: &mut PgConnection, sql_guild_id: i64) -> QueryResult<()> {
+	db_connection.transaction(|db_connection| {
+		diesel::delete(published_messages::table)
+			.filter(published_messages::guild_id.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(published_embed_summaries::table)
+			.filter(published_embed_summaries::guild_id.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(publish_webhooks::table)
+			.filter(publish_webhooks::guild_id.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(partner_role_exempt_users::table)
+			.filter(partner_role_exempt_users::guild_id.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(partner_role_exempt_roles::table)
+			.filter(partner_role_exempt_roles::guild_id.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(partnership_requests::table)
+			.filter(
+				partnership_requests::requesting_guild
+					.eq(sql_guild_id)
+					.or(partnership_requests::requested_guild.eq(sql_guild_id)),
+			)
+			.execute(db_connection)?;
+		diesel::delete(embed_data::table)
+			.filter(embed_data::guild.eq(sql_guild_id))
+			.execute(db_connection)?;
+
+		// Partner users, self-representatives, and pending representative changes are removed along with their partners
+		diesel::delete(partners::table)
+			.filter(partners::guild.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(archived_partners::table)
+			.filter(archived_partners::guild.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(partner_categories::table)
+			.filter(partner_categories::guild_id.eq(sql_guild_id))
+			.execute(db_connection)?;
+		diesel::delete(guild_settings::table)
+			.filter(guild_settings::guild_id.eq(sql_guild_id))
+			.execute(db_connection)?;
+		Ok(())
+	})
+}
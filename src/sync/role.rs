@@ -1,25 +1,68 @@
 use crate::database::get_database_connection;
 use crate::models::GuildSettings;
-use crate::schema::{guild_settings, partner_users, partners};
+use crate::schema::{guild_settings, partner_role_exempt_roles, partner_role_exempt_users, partner_users, partners};
+use crate::utils::partner_status::PartnerStatus;
+use crate::utils::staff_log::{log_embed, send_staff_log, LogKind};
 use diesel::prelude::*;
 use miette::IntoDiagnostic;
 use serenity::client::Context;
 use serenity::futures::StreamExt;
+use serenity::model::guild::Member;
 use serenity::model::id::{GuildId, RoleId, UserId};
 use std::collections::HashSet;
 use std::time::Duration;
 use tokio::time::interval;
 
+/// The users and roles for a guild whose partner role is managed manually instead of by the bot
+pub struct RoleExemptions {
+	users: HashSet<UserId>,
+	roles: HashSet<RoleId>,
+}
+
+impl RoleExemptions {
+	pub fn load(guild: GuildId, db_connection: &mut PgConnection) -> miette::Result<Self> {
+		let sql_guild_id = guild.get() as i64;
+		let users: Vec<i64> = partner_role_exempt_users::table
+			.filter(partner_role_exempt_users::guild_id.eq(sql_guild_id))
+			.select(partner_role_exempt_users::user_id)
+			.load(db_connection)
+			.into_diagnostic()?;
+		let roles: Vec<i64> = partner_role_exempt_roles::table
+			.filter(partner_role_exempt_roles::guild_id.eq(sql_guild_id))
+			.select(partner_role_exempt_roles::role_id)
+			.load(db_connection)
+			.into_diagnostic()?;
+
+		Ok(Self {
+			users: users.into_iter().map(|id| UserId::new(id as u64)).collect(),
+			roles: roles.into_iter().map(|id| RoleId::new(id as u64)).collect(),
+		})
+	}
+
+	/// Whether the bot should leave the partner role for this member alone
+	pub fn is_exempt(&self, member: &Member) -> bool {
+		self.users.contains(&member.user.id) || member.roles.iter().any(|role| self.roles.contains(role))
+	}
+}
+
 pub async fn sync_role_for_guild(ctx: &Context, guild: GuildId, role: RoleId) -> miette::Result<()> {
 	let db_connection = get_database_connection(ctx).await;
 	let sql_guild_id = guild.get() as i64;
-	let guild_partners: HashSet<UserId> = {
+	let role_statuses: Vec<&str> = PartnerStatus::ROLE_GRANTING
+		.iter()
+		.map(|status| status.as_str())
+		.collect();
+	let (guild_partners, exemptions) = {
 		let mut db_connection = db_connection.lock().await;
 		let partners: Vec<i64> = partner_users::table
 			.filter(
 				partner_users::partnership_id.eq_any(
 					partners::table
-						.filter(partners::guild.eq(sql_guild_id))
+						.filter(
+							partners::guild
+								.eq(sql_guild_id)
+								.and(partners::status.eq_any(&role_statuses)),
+						)
 						.select(partners::partnership_id),
 				),
 			)
@@ -27,26 +70,66 @@ pub async fn sync_role_for_guild(ctx: &Context, guild: GuildId, role: RoleId) ->
 			.distinct()
 			.load(&mut *db_connection)
 			.into_diagnostic()?;
-		partners.into_iter().map(|id| UserId::new(id as u64)).collect()
+		let partners: HashSet<UserId> = partners.into_iter().map(|id| UserId::new(id as u64)).collect();
+		let exemptions = RoleExemptions::load(guild, &mut db_connection)?;
+
+		(partners, exemptions)
 	};
 
+	let mut added_users: Vec<UserId> = Vec::new();
+	let mut removed_users: Vec<UserId> = Vec::new();
 	let mut members = guild.members_iter(&ctx.http).boxed();
 	while let Some(member) = members.next().await {
 		let member = member.into_diagnostic()?;
+		if exemptions.is_exempt(&member) {
+			continue;
+		}
 		let is_partner = guild_partners.contains(&member.user.id);
-		let has_partner_role = member.roles.iter().any(|role_id| *role_id == role);
+		let has_partner_role = member.roles.contains(&role);
 		if is_partner != has_partner_role {
 			if is_partner {
 				member.add_role(&ctx.http, role).await.into_diagnostic()?;
+				added_users.push(member.user.id);
 			} else {
 				member.remove_role(&ctx.http, role).await.into_diagnostic()?;
+				removed_users.push(member.user.id);
 			}
 		}
 	}
 
+	if !added_users.is_empty() || !removed_users.is_empty() {
+		let mut embed = log_embed(LogKind::Changed, "Partner role synchronized", None).field(
+			"Role",
+			format!("<@&{}>", role.get()),
+			true,
+		);
+		if !added_users.is_empty() {
+			embed = embed.field("Added to", user_list(&added_users), false);
+		}
+		if !removed_users.is_empty() {
+			embed = embed.field("Removed from", user_list(&removed_users), false);
+		}
+		send_staff_log(ctx, guild, embed).await?;
+	}
+
 	Ok(())
 }
 
+/// Formats a list of users for a log embed field, keeping within Discord's field length limit
+fn user_list(users: &[UserId]) -> String {
+	const MAX_LISTED_USERS: usize = 30;
+
+	let mut lines: Vec<String> = users
+		.iter()
+		.take(MAX_LISTED_USERS)
+		.map(|user| format!("- <@{}>", user.get()))
+		.collect();
+	if users.len() > MAX_LISTED_USERS {
+		lines.push(format!("- ...and {} more", users.len() - MAX_LISTED_USERS));
+	}
+	lines.join("\n")
+}
+
 pub async fn sync_all_roles_task(ctx: &Context) -> miette::Result<()> {
 	let mut interval = interval(Duration::from_secs(21600));
 	let db_connection = get_database_connection(ctx).await;
@@ -57,7 +140,11 @@ pub async fn sync_all_roles_task(ctx: &Context) -> miette::Result<()> {
 		let guilds_with_roles: Vec<GuildSettings> = {
 			let mut db_connection = db_connection.lock().await;
 			guild_settings::table
-				.filter(guild_settings::partner_role.is_not_null())
+				.filter(
+					guild_settings::partner_role
+						.is_not_null()
+						.and(guild_settings::departed_at.is_null()),
+				)
 				.load(&mut *db_connection)
 				.into_diagnostic()?
 		};
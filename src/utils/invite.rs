@@ -0,0 +1,40 @@
+use serenity::client::Context;
+use serenity::model::invite::{Invite, InviteGuild};
+use serenity::utils::parse_invite;
+
+/// A partner invite that was checked to be usable for a partner listing
+pub struct PartnerInvite {
+	pub code: String,
+	pub guild: InviteGuild,
+	pub member_count: Option<i32>,
+}
+
+/// Resolves and checks an invite link for use as a partner invite. If the invite can't be used, returns the reason to
+/// show to the user.
+pub async fn resolve_partner_invite(ctx: &Context, invite_link: &str) -> Result<PartnerInvite, &'static str> {
+	let invite_code = parse_invite(invite_link);
+
+	// Sometimes, when parsing the invite code, it can maintain an initial slash before the actual code.
+	// Somehow, this doesn't seem to break anything in Serenity, and Discord seems to accept it just fine (or Serenity
+	// removes the slash), but we want not to have it.
+	let invite_code = match invite_code.strip_prefix('/') {
+		Some(code) => code,
+		None => invite_code,
+	};
+
+	let Ok(invite) = Invite::get(ctx, invite_code, true, true, None).await else {
+		return Err("The invite link is invalid.");
+	};
+	let Some(guild) = invite.guild else {
+		return Err("The invite link is invalid; could not retrieve a server for it.");
+	};
+	if invite.expires_at.is_some() {
+		return Err("The invite link is not permanent.");
+	}
+
+	Ok(PartnerInvite {
+		code: invite_code.to_string(),
+		guild,
+		member_count: invite.approximate_member_count.map(|count| count as i32),
+	})
+}
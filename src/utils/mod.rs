@@ -1,2 +1,8 @@
+pub mod invite;
+pub mod mutual;
 pub mod pagination;
+pub mod partner_archive;
+pub mod partner_changes;
+pub mod partner_status;
 pub mod setup_check;
+pub mod staff_log;
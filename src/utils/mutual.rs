@@ -0,0 +1,82 @@
+use crate::models::Partner;
+use crate::schema::{guild_settings, partners};
+use diesel::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Whether a partner server also lists us as a partner
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mutuality {
+	/// The partner server uses this bot and lists us as a partner
+	Mutual,
+	/// The partner server uses this bot but doesn't list us as a partner
+	OneSided,
+	/// The partner server doesn't use this bot, so we can't tell
+	Unknown,
+}
+
+impl Mutuality {
+	pub fn description(&self) -> &'static str {
+		match self {
+			Self::Mutual => "Mutual; they list us as a partner too",
+			Self::OneSided => "One-sided; they use this bot but don't list us as a partner",
+			Self::Unknown => "Unknown; they don't use this bot",
+		}
+	}
+}
+
+/// How a guild's partnerships line up with the partner lists of other guilds using this bot
+pub struct MutualityReport {
+	/// The mutuality of each of the guild's partners, by partnership ID
+	pub partners: HashMap<String, Mutuality>,
+	/// Guilds using this bot that list this guild as a partner without being in this guild's partner list
+	pub unreciprocated_guilds: Vec<i64>,
+}
+
+impl MutualityReport {
+	pub fn load(db_connection: &mut PgConnection, sql_guild_id: i64) -> QueryResult<Self> {
+		let our_partners: Vec<Partner> = partners::table
+			.filter(partners::guild.eq(sql_guild_id))
+			.load(db_connection)?;
+		let our_partner_guilds: HashSet<i64> = our_partners.iter().map(|partner| partner.partner_guild).collect();
+
+		// Guilds the bot has left keep their data for a while, but their partner lists are no longer maintained
+		let managed_guilds: Vec<i64> = guild_settings::table
+			.filter(guild_settings::departed_at.is_null())
+			.select(guild_settings::guild_id)
+			.load(db_connection)?;
+		let managed_guilds: HashSet<i64> = managed_guilds.into_iter().collect();
+
+		let guilds_listing_us: Vec<i64> = partners::table
+			.filter(partners::partner_guild.eq(sql_guild_id))
+			.select(partners::guild)
+			.load(db_connection)?;
+		let guilds_listing_us: HashSet<i64> = guilds_listing_us
+			.into_iter()
+			.filter(|guild| managed_guilds.contains(guild))
+			.collect();
+
+		let partners = our_partners
+			.into_iter()
+			.map(|partner| {
+				let mutuality = if guilds_listing_us.contains(&partner.partner_guild) {
+					Mutuality::Mutual
+				} else if managed_guilds.contains(&partner.partner_guild) {
+					Mutuality::OneSided
+				} else {
+					Mutuality::Unknown
+				};
+				(partner.partnership_id, mutuality)
+			})
+			.collect();
+		let mut unreciprocated_guilds: Vec<i64> = guilds_listing_us
+			.into_iter()
+			.filter(|guild| !our_partner_guilds.contains(guild))
+			.collect();
+		unreciprocated_guilds.sort();
+
+		Ok(Self {
+			partners,
+			unreciprocated_guilds,
+		})
+	}
+}
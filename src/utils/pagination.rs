@@ -1,12 +1,15 @@
-use crate::models::Partner;
+use crate::models::{ArchivedPartner, Partner};
+use crate::utils::partner_status::PartnerStatus;
 use serenity::builder::CreateSelectMenuOption;
+use serenity::model::Timestamp;
+use std::time::UNIX_EPOCH;
 
 const PARTNER_PAGE_LEN: usize = 23;
 
 /// Gets the highest page number for a partner list
-pub fn max_partner_page(partners: &[Partner]) -> usize {
+pub fn max_partner_page<T>(partners: &[T]) -> usize {
 	let mut max_page = partners.len() / PARTNER_PAGE_LEN;
-	if partners.len() % PARTNER_PAGE_LEN == 0 {
+	if partners.len().is_multiple_of(PARTNER_PAGE_LEN) {
 		max_page = max_page.saturating_sub(1);
 	}
 	max_page
@@ -33,6 +36,58 @@ pub fn get_partners_for_page(
 		.take(PARTNER_PAGE_LEN)
 	{
 		let mut option = CreateSelectMenuOption::new(&partner.display_name, &partner.partnership_id);
+		if let Ok(status) = partner.status.parse::<PartnerStatus>() {
+			if !status.is_listed() {
+				option = option.description(status.display_name());
+			}
+		}
+		if partner.partnership_id == default_selection_id {
+			option = option.default_selection(true);
+		}
+		options.push(option);
+	}
+	if page_number < max_partner_page(partners) {
+		options.push(CreateSelectMenuOption::new("Next Page", ">"));
+	}
+
+	options
+}
+
+/// Gets the archived partner list for a particular page number, showing when each partner was removed
+pub fn get_archived_partners_for_page(
+	partners: &[ArchivedPartner],
+	page_number: usize,
+	default_selection_id: &str,
+) -> Vec<CreateSelectMenuOption> {
+	if partners.is_empty() {
+		return Vec::new();
+	}
+
+	let mut options: Vec<CreateSelectMenuOption> = Vec::with_capacity(PARTNER_PAGE_LEN + 2);
+	if page_number > 0 {
+		options.push(CreateSelectMenuOption::new("Previous Page", "<"));
+	}
+	for partner in partners
+		.iter()
+		.skip(page_number * PARTNER_PAGE_LEN)
+		.take(PARTNER_PAGE_LEN)
+	{
+		let removed_at = partner
+			.removed_at
+			.duration_since(UNIX_EPOCH)
+			.ok()
+			.and_then(|since_epoch| Timestamp::from_unix_timestamp(since_epoch.as_secs() as i64).ok())
+			.map(|timestamp| timestamp.to_string())
+			.unwrap_or_default();
+		// Timestamps format as RFC 3339, which starts with the date
+		let mut description = format!("Removed {}", removed_at.get(..10).unwrap_or("at an unknown time"));
+		if !partner.removal_reason.is_empty() {
+			description = format!("{}: {}", description, partner.removal_reason);
+		}
+		// Select menu option descriptions are limited to 100 characters
+		let description: String = description.chars().take(100).collect();
+		let mut option =
+			CreateSelectMenuOption::new(&partner.display_name, &partner.partnership_id).description(description);
 		if partner.partnership_id == default_selection_id {
 			option = option.default_selection(true);
 		}
@@ -0,0 +1,132 @@
+use crate::models::{
+	ArchivedPartner, ArchivedPartnerSelfUser, ArchivedPartnerUser, Partner, PartnerSelfUser, PartnerUser,
+};
+use crate::schema::{
+	archived_partner_self_users, archived_partner_users, archived_partners, partner_self_users, partner_users, partners,
+};
+use diesel::prelude::*;
+use std::time::SystemTime;
+
+/// Moves a partner and its representatives into the archive, removing it from the active partner list
+pub fn archive_partner(
+	db_connection: &mut PgConnection,
+	partnership_id: &str,
+	removal_reason: &str,
+	removed_by: Option<i64>,
+) -> QueryResult<()> {
+	db_connection.transaction(|db_connection| {
+		let partner: Partner = partners::table.find(partnership_id).first(db_connection)?;
+		let reps: Vec<PartnerUser> = partner_users::table
+			.filter(partner_users::partnership_id.eq(partnership_id))
+			.load(db_connection)?;
+		let self_reps: Vec<PartnerSelfUser> = partner_self_users::table
+			.filter(partner_self_users::partnership.eq(partnership_id))
+			.load(db_connection)?;
+
+		let archived_partner = ArchivedPartner {
+			partnership_id: partner.partnership_id,
+			guild: partner.guild,
+			category: Some(partner.category),
+			partner_guild: partner.partner_guild,
+			display_name: partner.display_name,
+			invite_code: partner.invite_code,
+			description: partner.description,
+			emoji: partner.emoji,
+			status: partner.status,
+			removal_reason: removal_reason.to_string(),
+			removed_at: SystemTime::now(),
+			removed_by,
+			partner_guild_name: partner.partner_guild_name,
+		};
+		let archived_reps: Vec<ArchivedPartnerUser> = reps
+			.into_iter()
+			.map(|rep| ArchivedPartnerUser {
+				partnership_id: rep.partnership_id,
+				user_id: rep.user_id,
+			})
+			.collect();
+		let archived_self_reps: Vec<ArchivedPartnerSelfUser> = self_reps
+			.into_iter()
+			.map(|rep| ArchivedPartnerSelfUser {
+				partnership: rep.partnership,
+				user_id: rep.user_id,
+			})
+			.collect();
+
+		diesel::insert_into(archived_partners::table)
+			.values(archived_partner)
+			.execute(db_connection)?;
+		diesel::insert_into(archived_partner_users::table)
+			.values(archived_reps)
+			.execute(db_connection)?;
+		diesel::insert_into(archived_partner_self_users::table)
+			.values(archived_self_reps)
+			.execute(db_connection)?;
+
+		// Representatives and self-representatives are removed along with the partner
+		diesel::delete(partners::table)
+			.filter(partners::partnership_id.eq(partnership_id))
+			.execute(db_connection)?;
+
+		Ok(())
+	})
+}
+
+/// Moves an archived partner and its representatives back into the partner list in the given category. Fails with a
+/// unique violation if the partner server or display name has since been reused.
+pub fn restore_partner(db_connection: &mut PgConnection, partnership_id: &str, category: &str) -> QueryResult<()> {
+	db_connection.transaction(|db_connection| {
+		let archived_partner: ArchivedPartner = archived_partners::table.find(partnership_id).first(db_connection)?;
+		let archived_reps: Vec<ArchivedPartnerUser> = archived_partner_users::table
+			.filter(archived_partner_users::partnership_id.eq(partnership_id))
+			.load(db_connection)?;
+		let archived_self_reps: Vec<ArchivedPartnerSelfUser> = archived_partner_self_users::table
+			.filter(archived_partner_self_users::partnership.eq(partnership_id))
+			.load(db_connection)?;
+
+		let partner = Partner {
+			partnership_id: archived_partner.partnership_id,
+			guild: archived_partner.guild,
+			category: category.to_string(),
+			partner_guild: archived_partner.partner_guild,
+			display_name: archived_partner.display_name,
+			invite_code: archived_partner.invite_code,
+			description: archived_partner.description,
+			emoji: archived_partner.emoji,
+			status: archived_partner.status,
+			partner_guild_name: archived_partner.partner_guild_name,
+			member_count: None,
+		};
+		let reps: Vec<PartnerUser> = archived_reps
+			.into_iter()
+			.map(|rep| PartnerUser {
+				partnership_id: rep.partnership_id,
+				user_id: rep.user_id,
+			})
+			.collect();
+		let self_reps: Vec<PartnerSelfUser> = archived_self_reps
+			.into_iter()
+			.map(|rep| PartnerSelfUser {
+				partnership: rep.partnership,
+				user_id: rep.user_id,
+			})
+			.collect();
+
+		diesel::insert_into(partners::table)
+			.values(partner)
+			.execute(db_connection)?;
+		diesel::insert_into(partner_users::table)
+			.values(reps)
+			.execute(db_connection)?;
+		diesel::insert_into(partner_self_users::table)
+			.values(self_reps)
+			.execute(db_connection)?;
+
+		// Archived representatives and self-representatives are removed along with the archived partner
+		diesel::delete(archived_partners::table)
+			.filter(archived_partners::partnership_id.eq(partnership_id))
+			.execute(db_connection)?;
+
+		Ok(())
+	})
+}
@@ -0,0 +1,37 @@
+use crate::models::{Partner, PendingPartnerChange};
+use crate::schema::partners;
+use diesel::prelude::*;
+use serenity::builder::CreateEmbed;
+
+/// Applies a representative's change to their partner's listing, leaving fields the change doesn't include as they
+/// are. Returns the updated partner.
+pub fn apply_partner_change(db_connection: &mut PgConnection, change: &PendingPartnerChange) -> QueryResult<Partner> {
+	let partner: Partner = partners::table.find(&change.partnership_id).first(db_connection)?;
+	diesel::update(partners::table)
+		.filter(partners::partnership_id.eq(&change.partnership_id))
+		.set((
+			partners::display_name.eq(change.display_name.as_ref().unwrap_or(&partner.display_name)),
+			partners::description.eq(change.description.as_ref().unwrap_or(&partner.description)),
+			partners::invite_code.eq(change.invite_code.as_ref().unwrap_or(&partner.invite_code)),
+			partners::partner_guild_name.eq(change
+				.partner_guild_name
+				.as_ref()
+				.unwrap_or(&partner.partner_guild_name)),
+		))
+		.get_result(db_connection)
+}
+
+/// Adds a field to a staff log entry for each part of the partner listing that a change includes
+pub fn add_change_fields(mut embed: CreateEmbed, change: &PendingPartnerChange) -> CreateEmbed {
+	if let Some(display_name) = &change.display_name {
+		embed = embed.field("New name", display_name, true);
+	}
+	if let Some(invite_code) = &change.invite_code {
+		embed = embed.field("New invite", format!("https://discord.gg/{}", invite_code), true);
+	}
+	if let Some(description) = &change.description {
+		let shown_description = if description.is_empty() { "None" } else { description };
+		embed = embed.field("New description", shown_description, false);
+	}
+	embed
+}
@@ -0,0 +1,68 @@
+use miette::bail;
+use std::str::FromStr;
+
+/// Where a current partnership is in its lifecycle. Partnerships that have ended are archived instead of having a
+/// status.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PartnerStatus {
+	/// Agreed on but not started; hidden from the partner list and doesn't grant the partner role
+	Pending,
+	Active,
+	/// Temporarily paused; hidden from the partner list, but representatives keep the partner role
+	Hiatus,
+}
+
+impl PartnerStatus {
+	pub const ALL: [Self; 3] = [Self::Pending, Self::Active, Self::Hiatus];
+
+	/// The statuses whose representatives get the partner role
+	pub const ROLE_GRANTING: [Self; 2] = [Self::Active, Self::Hiatus];
+
+	/// The value stored in the database
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::Pending => "pending",
+			Self::Active => "active",
+			Self::Hiatus => "hiatus",
+		}
+	}
+
+	pub fn display_name(&self) -> &'static str {
+		match self {
+			Self::Pending => "Pending",
+			Self::Active => "Active",
+			Self::Hiatus => "On hiatus",
+		}
+	}
+
+	pub fn is_listed(&self) -> bool {
+		*self == Self::Active
+	}
+
+	pub fn grants_role(&self) -> bool {
+		Self::ROLE_GRANTING.contains(self)
+	}
+
+	/// Whether a partner with this status can be moved to the new status. Pending partnerships have to start before
+	/// they can go on hiatus.
+	pub fn can_change_to(&self, new_status: Self) -> bool {
+		match (self, new_status) {
+			(old_status, new_status) if *old_status == new_status => false,
+			(Self::Pending, Self::Hiatus) => false,
+			_ => true,
+		}
+	}
+}
+
+impl FromStr for PartnerStatus {
+	type Err = miette::Report;
+
+	fn from_str(value: &str) -> Result<Self, Self::Err> {
+		match value {
+			"pending" => Ok(Self::Pending),
+			"active" => Ok(Self::Active),
+			"hiatus" => Ok(Self::Hiatus),
+			_ => bail!("Unknown partner status: {}", value),
+		}
+	}
+}
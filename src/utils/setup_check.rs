@@ -7,7 +7,7 @@ use serenity::client::Context;
 use serenity::model::application::CommandInteraction;
 use serenity::model::id::GuildId;
 
-pub const GUILD_NOT_SET_UP: &str = "This server hasn't been set up yet; use `/setup` to set up this server.";
+pub const GUILD_NOT_SET_UP: &str = "This server hasn't been set up yet; use `/setup start` to set up this server.";
 
 /// Checks whether a guild has set up the bot. Only necessary if the guild_settings table isn't queried anyway.
 fn guild_is_set_up(guild: GuildId, db_connection: &mut PgConnection) -> miette::Result<bool> {
@@ -0,0 +1,99 @@
+use crate::database::get_database_connection;
+use crate::schema::guild_settings;
+use diesel::prelude::*;
+use miette::IntoDiagnostic;
+use serenity::builder::{CreateActionRow, CreateEmbed, CreateMessage};
+use serenity::client::Context;
+use serenity::model::channel::Message;
+use serenity::model::id::{ChannelId, GuildId, UserId};
+use serenity::model::Timestamp;
+
+/// The type of event being logged; determines the color of the log embed
+pub enum LogKind {
+	Added,
+	Changed,
+	Removed,
+	Failed,
+}
+
+impl LogKind {
+	fn color(&self) -> u32 {
+		match self {
+			Self::Added => 0x2ecc71,
+			Self::Changed => 0x3498db,
+			Self::Removed => 0xe67e22,
+			Self::Failed => 0xe74c3c,
+		}
+	}
+}
+
+/// Creates the base embed for a staff log entry. If the event was caused by a user, that user should be passed as the
+/// actor; otherwise, the event is attributed to the bot.
+pub fn log_embed(kind: LogKind, title: &str, actor: Option<UserId>) -> CreateEmbed {
+	let embed = CreateEmbed::new()
+		.title(title)
+		.color(kind.color())
+		.timestamp(Timestamp::now());
+	match actor {
+		Some(user) => embed.field("By", format!("<@{}>", user.get()), true),
+		None => embed,
+	}
+}
+
+/// Sends an entry to the guild's staff log channel, if one is configured
+pub async fn send_staff_log(ctx: &Context, guild: GuildId, embed: CreateEmbed) -> miette::Result<()> {
+	send_staff_log_with_components(ctx, guild, embed, Vec::new()).await
+}
+
+/// Sends an entry to the guild's staff log channel with components (such as buttons) attached, if a log channel is
+/// configured
+pub async fn send_staff_log_with_components(
+	ctx: &Context,
+	guild: GuildId,
+	embed: CreateEmbed,
+	components: Vec<CreateActionRow>,
+) -> miette::Result<()> {
+	if let Some(channel) = staff_log_channel(ctx, guild).await? {
+		let message = CreateMessage::new().embed(embed).components(components);
+
+		// Ignore permission errors; the log channel shouldn't prevent anything else from working
+		let _ = channel.send_message(&ctx.http, message).await;
+	}
+
+	Ok(())
+}
+
+/// Sends an entry to the guild's staff log channel with components attached, for entries that something else depends
+/// on being delivered. Unlike `send_staff_log_with_components`, failing to send is returned as an error. Returns the
+/// sent message, or `None` if no log channel is configured.
+pub async fn deliver_staff_log_with_components(
+	ctx: &Context,
+	guild: GuildId,
+	embed: CreateEmbed,
+	components: Vec<CreateActionRow>,
+) -> miette::Result<Option<Message>> {
+	let Some(channel) = staff_log_channel(ctx, guild).await? else {
+		return Ok(None);
+	};
+	let message = CreateMessage::new().embed(embed).components(components);
+	let sent_message = channel.send_message(&ctx.http, message).await.into_diagnostic()?;
+	Ok(Some(sent_message))
+}
+
+/// Gets the guild's staff log channel, if one is configured
+async fn staff_log_channel(ctx: &Context, guild: GuildId) -> miette::Result<Option<ChannelId>> {
+	let db_connection = get_database_connection(ctx).await;
+	let sql_guild_id = guild.get() as i64;
+	let log_channel: Option<Option<i64>> = {
+		let mut db_connection = db_connection.lock().await;
+		guild_settings::table
+			.find(sql_guild_id)
+			.select(guild_settings::log_channel)
+			.first(&mut *db_connection)
+			.optional()
+			.into_diagnostic()?
+	};
+	Ok(log_channel
+		.flatten()
+		.map(|channel_id| ChannelId::new(channel_id as u64)))
+}